@@ -4,10 +4,30 @@
 #[cfg_attr(target_os = "macos", path = "macos/mod.rs")]
 #[cfg_attr(target_os = "linux", path = "linux/mod.rs")]
 #[cfg_attr(target_os = "windows", path = "windows/mod.rs")]
+// Only the mock answers dialogs with the `mock` feature
+#[cfg_attr(feature = "mock", allow(dead_code))]
 mod os_dialog;
+mod progress;
+mod secret;
 
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle};
+#[cfg(feature = "mock")]
+pub mod mock;
+
+#[cfg(target_os = "linux")]
+pub use os_dialog::backend::{Backend, set_backend};
+pub use progress::Progress;
+pub use secret::Secret;
+
+use raw_window_handle::{
+    DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, WindowHandle,
+};
+use std::collections::HashMap;
 use std::io;
+#[cfg(not(feature = "mock"))]
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+#[cfg(not(feature = "mock"))]
+use std::thread;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,18 +38,46 @@ pub enum BlockingDialogError {
     Io(#[from] io::Error),
     #[error("Handle error: {0}")]
     Handle(HandleError),
+    #[error("D-Bus error: {0}")]
+    DBus(String),
+    #[error("No dialog backend available (tried: {0})")]
+    NoBackend(String),
+    #[error("Unknown dialog backend: {0}")]
+    UnknownBackend(String),
 }
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BlockingDialogLevel {
     Info,
     Warning,
     Error,
 }
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
+/// Any dialog, so that helpers can be written once for every kind
+pub trait Dialog {
+    type Output;
+
+    fn show(&self) -> Result<Self::Output, BlockingDialogError>;
+}
+
+/// Use as the window to show a dialog that isn't attached to any window
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoParent;
+
+impl HasWindowHandle for NoParent {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        Err(HandleError::Unavailable)
+    }
+}
+
+impl HasDisplayHandle for NoParent {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        Err(HandleError::Unavailable)
+    }
+}
+
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct BlockingAlertDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
     pub window: W,
     pub title: &'a str,
@@ -37,13 +85,223 @@ pub struct BlockingAlertDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
     pub level: BlockingDialogLevel,
 }
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct BlockingConfirmDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
     pub window: W,
     pub title: &'a str,
     pub message: &'a str,
     pub level: BlockingDialogLevel,
+    pub accept_label: Option<&'a str>,
+    pub cancel_label: Option<&'a str>,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingQuestionDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub message: &'a str,
+    pub level: BlockingDialogLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockingQuestionDialogResponse {
+    Yes,
+    No,
+    /// Also returned when the dialog is closed without answering
+    Cancel,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingMessageDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub message: &'a str,
+    pub level: BlockingDialogLevel,
+    /// Button labels in display order, a single "OK" if empty
+    pub buttons: &'a [&'a str],
+    pub default_button: usize,
+    /// Returned when the dialog is dismissed, the last button if `None`
+    pub cancel_button: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingInputDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub message: &'a str,
+    /// Text the entry starts with
+    pub default_text: Option<&'a str>,
+    /// Hint shown while the entry is empty, where the backend supports it
+    pub placeholder: Option<&'a str>,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingPasswordDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub message: &'a str,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingLoginDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub message: &'a str,
+    /// Prefilled username, not supported by zenity
+    pub username: Option<&'a str>,
+}
+
+#[derive(Debug)]
+pub struct Credentials {
+    pub username: String,
+    pub password: Secret,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingFormDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub message: &'a str,
+    pub fields: &'a [BlockingFormDialogField<'a>],
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockingFormDialogField<'a> {
+    /// Key of the field's value in the returned map
+    pub id: &'a str,
+    pub label: &'a str,
+    pub kind: BlockingFormDialogFieldKind<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub enum BlockingFormDialogFieldKind<'a> {
+    Text,
+    Password,
+    Multiline,
+    /// Starts on the first option, except with zenity where one must be picked.
+    /// Showing a dropdown without options fails.
+    Dropdown(&'a [&'a str]),
+    Checkbox,
+    /// Starts on today's date
+    Date,
+}
+
+#[derive(Debug)]
+pub enum BlockingFormDialogValue {
+    /// Entered in a text or multiline field
+    Text(String),
+    Password(Secret),
+    /// Index of the chosen option
+    Dropdown(usize),
+    Checkbox(bool),
+    /// Year, month and day, with month and day counted from 1
+    Date(i32, u32, u32),
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingListDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub message: &'a str,
+    pub columns: &'a [&'a str],
+    /// One cell per column in each row
+    pub rows: &'a [&'a [&'a str]],
+    /// Indices of the rows selected at first, only the first one counts unless checklist
+    pub selected: &'a [usize],
+    pub mode: BlockingListDialogMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockingListDialogMode {
+    /// A single row, marked with radio buttons
+    Radio,
+    /// Any number of rows, marked with checkboxes
+    Checklist,
+    /// A single row, highlighted
+    Plain,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingProgressDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    /// Shown above the bar until the worker sets its own text
+    pub message: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockingProgressDialogOutcome<T> {
+    /// The worker ran to the end and returned this
+    Completed(T),
+    /// The user cancelled, what the worker returned is dropped
+    Cancelled,
+}
+
+/// An sRGB color, with 255 as fully opaque alpha
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// An opaque color
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::new(r, g, b, 255)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingColorDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub initial: Option<Rgba>,
+    /// Whether transparency can be picked, where the backend supports it
+    pub alpha: bool,
+}
+
+/// A calendar date, with month and day counted from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub const fn new(year: i32, month: u32, day: u32) -> Self {
+        Self { year, month, day }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockingDateDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
+    pub window: W,
+    pub title: &'a str,
+    pub message: &'a str,
+    /// Today's date when unset, moved within the bounds either way
+    pub initial: Option<Date>,
+    /// Earliest date that can be picked
+    pub min: Option<Date>,
+    /// Latest date that can be picked
+    pub max: Option<Date>,
 }
 
 #[derive(Debug, Clone)]
@@ -53,23 +311,930 @@ pub struct BlockingPickFilesDialogFilter<'a> {
 }
 
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct BlockingPickFilesDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
     pub window: W,
     pub title: &'a str,
     pub multiple: bool,
     pub filter: &'a [BlockingPickFilesDialogFilter<'a>],
+    pub starting_directory: Option<&'a Path>,
 }
 
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct BlockingPickDirectoryDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
     pub window: W,
     pub title: &'a str,
+    pub starting_directory: Option<&'a Path>,
 }
 
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct BlockingSaveFileDialog<'a, W: HasWindowHandle + HasDisplayHandle> {
     pub window: W,
     pub title: &'a str,
     pub default_filename: Option<&'a str>,
     pub filter: &'a [BlockingPickFilesDialogFilter<'a>],
+    pub starting_directory: Option<&'a Path>,
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingAlertDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            level: BlockingDialogLevel::Info,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn level(mut self, level: BlockingDialogLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn show(&self) -> Result<(), BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingAlertDialog<'a, W> {
+    type Output = ();
+
+    fn show(&self) -> Result<(), BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Alert {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            level: self.level,
+        })
+        .into_alert();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingConfirmDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            level: BlockingDialogLevel::Info,
+            accept_label: None,
+            cancel_label: None,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn level(mut self, level: BlockingDialogLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn accept_label(mut self, accept_label: &'a str) -> Self {
+        self.accept_label = Some(accept_label);
+        self
+    }
+
+    pub fn cancel_label(mut self, cancel_label: &'a str) -> Self {
+        self.cancel_label = Some(cancel_label);
+        self
+    }
+
+    pub fn show(&self) -> Result<bool, BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingConfirmDialog<'a, W> {
+    type Output = bool;
+
+    fn show(&self) -> Result<bool, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Confirm {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            level: self.level,
+            accept_label: self.accept_label.map(str::to_string),
+            cancel_label: self.cancel_label.map(str::to_string),
+        })
+        .into_confirm();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickFilesDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            multiple: false,
+            filter: &[],
+            starting_directory: None,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    pub fn filter(mut self, filter: &'a [BlockingPickFilesDialogFilter<'a>]) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn starting_directory(mut self, starting_directory: &'a Path) -> Self {
+        self.starting_directory = Some(starting_directory);
+        self
+    }
+
+    pub fn show(&self) -> Result<Vec<PathBuf>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingPickFilesDialog<'a, W> {
+    type Output = Vec<PathBuf>;
+
+    fn show(&self) -> Result<Vec<PathBuf>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::PickFiles {
+            title: self.title.to_string(),
+            multiple: self.multiple,
+            filter: mock::get_filter(self.filter),
+            starting_directory: self.starting_directory.map(Path::to_path_buf),
+        })
+        .into_files();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickDirectoryDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            starting_directory: None,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn starting_directory(mut self, starting_directory: &'a Path) -> Self {
+        self.starting_directory = Some(starting_directory);
+        self
+    }
+
+    pub fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingPickDirectoryDialog<'a, W> {
+    type Output = Option<PathBuf>;
+
+    fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::PickDirectory {
+            title: self.title.to_string(),
+            starting_directory: self.starting_directory.map(Path::to_path_buf),
+        })
+        .into_file();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingSaveFileDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            default_filename: None,
+            filter: &[],
+            starting_directory: None,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn default_filename(mut self, default_filename: &'a str) -> Self {
+        self.default_filename = Some(default_filename);
+        self
+    }
+
+    pub fn filter(mut self, filter: &'a [BlockingPickFilesDialogFilter<'a>]) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn starting_directory(mut self, starting_directory: &'a Path) -> Self {
+        self.starting_directory = Some(starting_directory);
+        self
+    }
+
+    pub fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingSaveFileDialog<'a, W> {
+    type Output = Option<PathBuf>;
+
+    fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::SaveFile {
+            title: self.title.to_string(),
+            default_filename: self.default_filename.map(str::to_string),
+            filter: mock::get_filter(self.filter),
+            starting_directory: self.starting_directory.map(Path::to_path_buf),
+        })
+        .into_file();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingQuestionDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            level: BlockingDialogLevel::Info,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn level(mut self, level: BlockingDialogLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn show(&self) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingQuestionDialog<'a, W> {
+    type Output = BlockingQuestionDialogResponse;
+
+    fn show(&self) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Question {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            level: self.level,
+        })
+        .into_question();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingMessageDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            level: BlockingDialogLevel::Info,
+            buttons: &[],
+            default_button: 0,
+            cancel_button: None,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn level(mut self, level: BlockingDialogLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn buttons(mut self, buttons: &'a [&'a str]) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    pub fn default_button(mut self, default_button: usize) -> Self {
+        self.default_button = default_button;
+        self
+    }
+
+    pub fn cancel_button(mut self, cancel_button: usize) -> Self {
+        self.cancel_button = Some(cancel_button);
+        self
+    }
+
+    pub fn show(&self) -> Result<usize, BlockingDialogError> {
+        Dialog::show(self)
+    }
+
+    pub(crate) fn get_buttons(&self) -> &[&'a str] {
+        if self.buttons.is_empty() {
+            &["OK"]
+        } else {
+            self.buttons
+        }
+    }
+
+    pub(crate) fn get_default_button(&self) -> usize {
+        self.default_button.min(self.get_buttons().len() - 1)
+    }
+
+    pub(crate) fn get_cancel_button(&self) -> usize {
+        let last = self.get_buttons().len() - 1;
+        self.cancel_button.unwrap_or(last).min(last)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingMessageDialog<'a, W> {
+    type Output = usize;
+
+    fn show(&self) -> Result<usize, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Message {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            level: self.level,
+            buttons: self.get_buttons().iter().map(|b| b.to_string()).collect(),
+            default_button: self.get_default_button(),
+            cancel_button: self.get_cancel_button(),
+        })
+        .into_button(self.get_default_button(), self.get_cancel_button());
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingInputDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            default_text: None,
+            placeholder: None,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn default_text(mut self, default_text: &'a str) -> Self {
+        self.default_text = Some(default_text);
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: &'a str) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    pub fn show(&self) -> Result<Option<String>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingInputDialog<'a, W> {
+    type Output = Option<String>;
+
+    fn show(&self) -> Result<Option<String>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Input {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            default_text: self.default_text.map(str::to_string),
+            placeholder: self.placeholder.map(str::to_string),
+        })
+        .into_text(self.default_text);
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPasswordDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn show(&self) -> Result<Option<Secret>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingPasswordDialog<'a, W> {
+    type Output = Option<Secret>;
+
+    fn show(&self) -> Result<Option<Secret>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Password {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+        })
+        .into_secret();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingLoginDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            username: None,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn username(mut self, username: &'a str) -> Self {
+        self.username = Some(username);
+        self
+    }
+
+    pub fn show(&self) -> Result<Option<Credentials>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingLoginDialog<'a, W> {
+    type Output = Option<Credentials>;
+
+    fn show(&self) -> Result<Option<Credentials>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Login {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            username: self.username.map(str::to_string),
+        })
+        .into_credentials();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingFormDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            fields: &[],
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn fields(mut self, fields: &'a [BlockingFormDialogField<'a>]) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    pub fn show(
+        &self,
+    ) -> Result<Option<HashMap<String, BlockingFormDialogValue>>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+
+    /// Every backend would return an index into an empty dropdown
+    fn check_fields(&self) -> Result<(), BlockingDialogError> {
+        let empty = self.fields.iter().find(|field| {
+            matches!(field.kind, BlockingFormDialogFieldKind::Dropdown(options) if options.is_empty())
+        });
+
+        match empty {
+            Some(field) => Err(BlockingDialogError::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Dropdown {} has no options", field.id),
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingFormDialog<'a, W> {
+    type Output = Option<HashMap<String, BlockingFormDialogValue>>;
+
+    fn show(
+        &self,
+    ) -> Result<Option<HashMap<String, BlockingFormDialogValue>>, BlockingDialogError> {
+        self.check_fields()?;
+
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Form {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            fields: mock::get_fields(self.fields),
+        })
+        .into_form();
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingListDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            columns: &[],
+            rows: &[],
+            selected: &[],
+            mode: BlockingListDialogMode::Plain,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn columns(mut self, columns: &'a [&'a str]) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn rows(mut self, rows: &'a [&'a [&'a str]]) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    pub fn selected(mut self, selected: &'a [usize]) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn mode(mut self, mode: BlockingListDialogMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the selected row indices in ascending order, empty if cancelled
+    pub fn show(&self) -> Result<Vec<usize>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+
+    /// Valid preselected rows, at most one unless checklist
+    pub(crate) fn get_selected(&self) -> Vec<usize> {
+        let selected = self
+            .selected
+            .iter()
+            .copied()
+            .filter(|i| *i < self.rows.len());
+
+        match self.mode {
+            BlockingListDialogMode::Checklist => selected.collect(),
+            _ => selected.take(1).collect(),
+        }
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingListDialog<'a, W> {
+    type Output = Vec<usize>;
+
+    fn show(&self) -> Result<Vec<usize>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::List {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            columns: self.columns.iter().map(|c| c.to_string()).collect(),
+            rows: self
+                .rows
+                .iter()
+                .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+                .collect(),
+            selected: self.get_selected(),
+            mode: self.mode,
+        })
+        .into_rows(self.get_selected());
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingColorDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            initial: None,
+            alpha: false,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn initial(mut self, initial: Rgba) -> Self {
+        self.initial = Some(initial);
+        self
+    }
+
+    pub fn alpha(mut self, alpha: bool) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Returns `None` if cancelled, the alpha is 255 unless `alpha` is set
+    pub fn show(&self) -> Result<Option<Rgba>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+
+    /// Makes a picked color opaque unless transparency was asked for
+    pub(crate) fn get_color(&self, color: Rgba) -> Rgba {
+        if self.alpha {
+            color
+        } else {
+            Rgba { a: 255, ..color }
+        }
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingColorDialog<'a, W> {
+    type Output = Option<Rgba>;
+
+    fn show(&self) -> Result<Option<Rgba>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        let color = mock::respond(mock::Request::Color {
+            title: self.title.to_string(),
+            initial: self.initial,
+            alpha: self.alpha,
+        })
+        .into_color(self.initial)?;
+
+        #[cfg(not(feature = "mock"))]
+        let color = self.show_native()?;
+
+        Ok(color.map(|color| self.get_color(color)))
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingDateDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+            initial: None,
+            min: None,
+            max: None,
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn initial(mut self, initial: Date) -> Self {
+        self.initial = Some(initial);
+        self
+    }
+
+    pub fn min(mut self, min: Date) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: Date) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Returns `None` if cancelled
+    pub fn show(&self) -> Result<Option<Date>, BlockingDialogError> {
+        Dialog::show(self)
+    }
+
+    /// Moves a date within the bounds, `max` wins if they cross
+    pub(crate) fn clamp(&self, date: Date) -> Date {
+        let date = self.min.map_or(date, |min| date.max(min));
+        self.max.map_or(date, |max| date.min(max))
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> Dialog for BlockingDateDialog<'a, W> {
+    type Output = Option<Date>;
+
+    fn show(&self) -> Result<Option<Date>, BlockingDialogError> {
+        #[cfg(feature = "mock")]
+        return mock::respond(mock::Request::Date {
+            title: self.title.to_string(),
+            message: self.message.to_string(),
+            initial: self.initial,
+            min: self.min,
+            max: self.max,
+        })
+        .into_date(self.initial.map(|initial| self.clamp(initial)));
+
+        #[cfg(not(feature = "mock"))]
+        self.show_native()
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingProgressDialog<'a, W> {
+    pub fn new(window: W) -> Self {
+        Self {
+            window,
+            title: "",
+            message: "",
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = message;
+        self
+    }
+
+    /// Shows the dialog while `work` runs on another thread, until it returns.
+    ///
+    /// Cancelling only sets [`Progress::is_cancelled`], the worker is still
+    /// waited for, though some backends close the dialog in the meantime.
+    pub fn run<T, F>(
+        &self,
+        work: F,
+    ) -> Result<BlockingProgressDialogOutcome<T>, BlockingDialogError>
+    where
+        T: Send,
+        F: FnOnce(&Progress) -> T + Send,
+    {
+        let progress = Progress::new();
+
+        // The worker only starts once a dialog can be shown
+        #[cfg(not(feature = "mock"))]
+        let native = self.prepare_native()?;
+
+        #[cfg(feature = "mock")]
+        {
+            let response = mock::respond(mock::Request::Progress {
+                title: self.title.to_string(),
+                message: self.message.to_string(),
+            });
+
+            if response.into_cancelled()? {
+                progress.cancel();
+            }
+
+            let result = work(&progress);
+            progress.finish();
+
+            if progress.is_cancelled() {
+                Ok(BlockingProgressDialogOutcome::Cancelled)
+            } else {
+                Ok(BlockingProgressDialogOutcome::Completed(result))
+            }
+        }
+
+        #[cfg(not(feature = "mock"))]
+        thread::scope(|scope| {
+            let worker = scope.spawn(|| {
+                // The dialog only closes once the worker is finished, even by panicking
+                let result = panic::catch_unwind(AssertUnwindSafe(|| work(&progress)));
+                progress.finish();
+                result
+            });
+
+            let shown = self.show_native(native, &progress);
+
+            // Without a dialog nobody can cancel, so the worker is told to stop
+            if shown.is_err() {
+                progress.cancel();
+            }
+
+            let result = match worker.join() {
+                Ok(Ok(result)) => result,
+                Ok(Err(payload)) | Err(payload) => panic::resume_unwind(payload),
+            };
+
+            shown?;
+
+            if progress.is_cancelled() {
+                Ok(BlockingProgressDialogOutcome::Cancelled)
+            } else {
+                Ok(BlockingProgressDialogOutcome::Completed(result))
+            }
+        })
+    }
 }
@@ -0,0 +1,18 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{kdialog, tty, zenity};
+use crate::{BlockingAlertDialog, BlockingDialogError};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingAlertDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<(), BlockingDialogError> {
+        match backend::select(&[Backend::Zenity, Backend::Kdialog, Backend::Tty])? {
+            Backend::Zenity => zenity::alert(self.title, self.message, self.level),
+            Backend::Kdialog => kdialog::alert(self.title, self.message, self.level),
+            Backend::Tty => tty::alert(self.title, self.message, self.level),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::dbus::{Connection, Value};
+use super::{subprocess, tty};
+use crate::BlockingDialogError;
+use std::env;
+use std::sync::Mutex;
+
+const ENV_VAR: &str = "BLOCKING_DIALOG_BACKEND";
+
+static OVERRIDE: Mutex<Option<Backend>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Portal,
+    Zenity,
+    Kdialog,
+    Tty,
+}
+
+impl Backend {
+    const ALL: [Backend; 4] = [
+        Backend::Portal,
+        Backend::Zenity,
+        Backend::Kdialog,
+        Backend::Tty,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Portal => "portal",
+            Backend::Zenity => "zenity",
+            Backend::Kdialog => "kdialog",
+            Backend::Tty => "tty",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|backend| backend.name() == name)
+    }
+
+    pub fn is_available(self) -> bool {
+        match self {
+            Backend::Portal => has_display() && portal_is_available(),
+            Backend::Zenity => has_display() && subprocess::is_available("zenity"),
+            Backend::Kdialog => has_display() && subprocess::is_available("kdialog"),
+            Backend::Tty => tty::is_available(),
+        }
+    }
+}
+
+/// Forces a backend for all subsequent dialogs, takes precedence over `BLOCKING_DIALOG_BACKEND`
+pub fn set_backend(backend: Option<Backend>) {
+    if let Ok(mut guard) = OVERRIDE.lock() {
+        *guard = backend;
+    }
+}
+
+fn has_display() -> bool {
+    env::var_os("DISPLAY").is_some() || env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+fn portal_is_available() -> bool {
+    let Ok(mut conn) = Connection::session() else {
+        return false;
+    };
+
+    // The portal is usually D-Bus activated, so it may not be running yet
+    ["ListNames", "ListActivatableNames"]
+        .into_iter()
+        .any(|method| {
+            let Ok(reply) = conn.call_method(
+                "org.freedesktop.DBus",
+                "/org/freedesktop/DBus",
+                "org.freedesktop.DBus",
+                method,
+                Vec::new(),
+            ) else {
+                return false;
+            };
+
+            reply
+                .body
+                .first()
+                .and_then(Value::as_array)
+                .unwrap_or_default()
+                .iter()
+                .any(|name| name.as_str() == Some("org.freedesktop.portal.Desktop"))
+        })
+}
+
+fn forced() -> Result<Option<Backend>, BlockingDialogError> {
+    if let Ok(guard) = OVERRIDE.lock()
+        && let Some(backend) = *guard
+    {
+        return Ok(Some(backend));
+    }
+
+    match env::var(ENV_VAR) {
+        Ok(name) if !name.is_empty() => Backend::from_name(&name)
+            .map(Some)
+            .ok_or(BlockingDialogError::UnknownBackend(name)),
+        _ => Ok(None),
+    }
+}
+
+/// Picks the first available backend among the supported ones, in the order they are given
+pub fn select(supported: &[Backend]) -> Result<Backend, BlockingDialogError> {
+    if let Some(backend) = forced()? {
+        return if backend.is_available() {
+            Ok(backend)
+        } else {
+            Err(BlockingDialogError::NoBackend(backend.name().to_string()))
+        };
+    }
+
+    let mut tried = Vec::new();
+    for &backend in supported {
+        if backend.is_available() {
+            return Ok(backend);
+        }
+        tried.push(backend.name());
+    }
+
+    Err(BlockingDialogError::NoBackend(tried.join(", ")))
+}
+
+pub fn unsupported(backend: Backend) -> BlockingDialogError {
+    BlockingDialogError::NoBackend(format!("{} (not supported by this dialog)", backend.name()))
+}
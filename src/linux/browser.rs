@@ -0,0 +1,613 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::terminal::{Key, Terminal, truncate};
+use super::tty::matches_filter;
+use crate::{BlockingDialogError, BlockingPickFilesDialogFilter};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub enum Mode<'a> {
+    Open { multiple: bool },
+    Save { default_filename: Option<&'a str> },
+    Directory,
+}
+
+struct Entry {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+}
+
+struct Browser<'a> {
+    title: &'a str,
+    mode: Mode<'a>,
+    filter: &'a [BlockingPickFilesDialogFilter<'a>],
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    /// Type-to-filter text when opening, the file name when saving
+    input: String,
+    cursor: usize,
+    scroll: usize,
+    /// When saving, whether Enter applies to the name instead of the list
+    name_focused: bool,
+    selected: Vec<PathBuf>,
+    status: String,
+}
+
+enum Action {
+    Continue,
+    Cancel,
+    Done(Vec<PathBuf>),
+}
+
+impl<'a> Browser<'a> {
+    fn new(
+        title: &'a str,
+        mode: Mode<'a>,
+        filter: &'a [BlockingPickFilesDialogFilter<'a>],
+        dir: PathBuf,
+    ) -> Self {
+        let (input, name_focused) = match mode {
+            Mode::Save { default_filename } => (default_filename.unwrap_or_default(), true),
+            _ => ("", false),
+        };
+
+        let mut browser = Browser {
+            title,
+            mode,
+            filter,
+            dir,
+            entries: Vec::new(),
+            input: input.to_string(),
+            cursor: 0,
+            scroll: 0,
+            name_focused,
+            selected: Vec::new(),
+            status: String::new(),
+        };
+
+        browser.refresh();
+        browser
+    }
+
+    fn is_saving(&self) -> bool {
+        matches!(self.mode, Mode::Save { .. })
+    }
+
+    fn refresh(&mut self) {
+        let mut entries = Vec::new();
+
+        if let Ok(read_dir) = fs::read_dir(&self.dir) {
+            for entry in read_dir.flatten() {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let is_dir = path.is_dir();
+
+                if name.starts_with('.') && !self.input.starts_with('.') {
+                    continue;
+                }
+
+                let visible = match self.mode {
+                    Mode::Directory => is_dir,
+                    _ => is_dir || matches_filter(&path, self.filter),
+                };
+
+                let matches_input =
+                    self.is_saving() || name.to_lowercase().contains(&self.input.to_lowercase());
+
+                if visible && matches_input {
+                    entries.push(Entry { name, path, is_dir });
+                }
+            }
+        }
+
+        entries.sort_by(|a, b| {
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
+        if let Mode::Directory = self.mode {
+            entries.insert(
+                0,
+                Entry {
+                    name: ".".to_string(),
+                    path: self.dir.clone(),
+                    is_dir: true,
+                },
+            );
+        }
+
+        if let Some(parent) = self.dir.parent() {
+            entries.insert(
+                0,
+                Entry {
+                    name: "..".to_string(),
+                    path: parent.to_path_buf(),
+                    is_dir: true,
+                },
+            );
+        }
+
+        self.entries = entries;
+        self.cursor = self.cursor.min(self.entries.len().saturating_sub(1));
+    }
+
+    fn change_dir(&mut self, dir: PathBuf) {
+        self.dir = dir;
+        self.cursor = 0;
+        self.scroll = 0;
+        self.status.clear();
+
+        if !self.is_saving() {
+            self.input.clear();
+        }
+
+        self.refresh();
+    }
+
+    fn render(&mut self, terminal: &mut Terminal) -> Result<(), BlockingDialogError> {
+        let (rows, cols) = terminal.size();
+        let list_rows = rows.saturating_sub(5).max(1);
+
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + list_rows {
+            self.scroll = self.cursor + 1 - list_rows;
+        }
+
+        let mut out = String::from("\x1b[H");
+
+        let dir = self.dir.display().to_string();
+        let label = if self.is_saving() { "Name" } else { "Filter" };
+        let input = format!("{label}: {}", self.input);
+
+        out.push_str(&format!(
+            "\x1b[1m{}\x1b[0m\x1b[K\r\n",
+            truncate(self.title, cols)
+        ));
+        out.push_str(&format!("{}\x1b[K\r\n", truncate(&dir, cols)));
+        out.push_str(&format!("{}\x1b[K\r\n", truncate(&input, cols)));
+
+        for row in 0..list_rows {
+            let Some(entry) = self.entries.get(self.scroll + row) else {
+                out.push_str("\x1b[K\r\n");
+                continue;
+            };
+
+            let mark = match self.mode {
+                Mode::Open { multiple: true } if !entry.is_dir => {
+                    if self.selected.contains(&entry.path) {
+                        "[x] "
+                    } else {
+                        "[ ] "
+                    }
+                }
+                _ => "",
+            };
+
+            let suffix = match (entry.name.as_str(), entry.is_dir) {
+                (".", _) => "  (select this directory)",
+                (_, true) => "/",
+                _ => "",
+            };
+
+            let line = format!("{mark}{}{suffix}", entry.name);
+            let line = truncate(&line, cols);
+
+            let highlighted = self.scroll + row == self.cursor && !self.name_focused;
+            if highlighted {
+                out.push_str(&format!("\x1b[7m{line}\x1b[0m\x1b[K\r\n"));
+            } else {
+                out.push_str(&format!("{line}\x1b[K\r\n"));
+            }
+        }
+
+        out.push_str(&format!("{}\x1b[K\r\n", truncate(&self.status, cols)));
+
+        let help = match self.mode {
+            Mode::Open { multiple: true } => {
+                "Enter: open/select  Space: mark  Left: up  Esc: cancel"
+            }
+            Mode::Open { multiple: false } => "Enter: open/select  Left: up  Esc: cancel",
+            Mode::Save { .. } => "Enter: open/save  Tab: edit name  Left: up  Esc: cancel",
+            Mode::Directory => "Enter: open/select  Left: up  Esc: cancel",
+        };
+        out.push_str(&format!("\x1b[2m{}\x1b[0m\x1b[J", truncate(help, cols)));
+
+        terminal.write_all(out.as_bytes())?;
+        terminal.flush()?;
+
+        Ok(())
+    }
+
+    fn submit(&mut self, terminal: &mut Terminal) -> Result<Action, BlockingDialogError> {
+        if self.is_saving() && self.name_focused {
+            return self.submit_name(terminal);
+        }
+
+        let Some(entry) = self.entries.get(self.cursor) else {
+            return Ok(Action::Continue);
+        };
+
+        if let Mode::Directory = self.mode
+            && entry.name == "."
+        {
+            return Ok(Action::Done(vec![entry.path.clone()]));
+        }
+
+        if entry.is_dir {
+            let path = entry.path.clone();
+            self.change_dir(path);
+            return Ok(Action::Continue);
+        }
+
+        match self.mode {
+            Mode::Open { multiple: true } if !self.selected.is_empty() => {
+                Ok(Action::Done(self.selected.clone()))
+            }
+            Mode::Save { .. } => {
+                self.input = entry.name.clone();
+                self.name_focused = true;
+                self.submit_name(terminal)
+            }
+            _ => Ok(Action::Done(vec![entry.path.clone()])),
+        }
+    }
+
+    fn submit_name(&mut self, terminal: &mut Terminal) -> Result<Action, BlockingDialogError> {
+        if self.input.is_empty() {
+            return Ok(Action::Continue);
+        }
+
+        let path = self.dir.join(&self.input);
+
+        if path.is_dir() {
+            self.input.clear();
+            self.change_dir(path);
+            return Ok(Action::Continue);
+        }
+
+        if !matches_filter(&path, self.filter) {
+            self.status = "Not an allowed file type".to_string();
+            return Ok(Action::Continue);
+        }
+
+        if path.exists() {
+            self.status = format!("{} exists, overwrite? [y/N]", self.input);
+            self.render(terminal)?;
+
+            let overwrite = matches!(terminal.read_key()?, Key::Char('y' | 'Y'));
+            self.status.clear();
+
+            if !overwrite {
+                return Ok(Action::Continue);
+            }
+        }
+
+        Ok(Action::Done(vec![path]))
+    }
+
+    fn handle_key(
+        &mut self,
+        key: Key,
+        terminal: &mut Terminal,
+    ) -> Result<Action, BlockingDialogError> {
+        let page = terminal.size().0.saturating_sub(5).max(1);
+        let last = self.entries.len().saturating_sub(1);
+
+        match key {
+            Key::Up => {
+                self.cursor = self.cursor.saturating_sub(1);
+                self.name_focused = false;
+            }
+            Key::Down => {
+                self.cursor = (self.cursor + 1).min(last);
+                self.name_focused = false;
+            }
+            Key::PageUp => self.cursor = self.cursor.saturating_sub(page),
+            Key::PageDown => self.cursor = (self.cursor + page).min(last),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = last,
+            Key::Left => {
+                if let Some(parent) = self.dir.parent() {
+                    let parent = parent.to_path_buf();
+                    self.change_dir(parent);
+                }
+            }
+            Key::Right => {
+                if let Some(entry) = self.entries.get(self.cursor)
+                    && entry.is_dir
+                    && entry.name != "."
+                {
+                    let path = entry.path.clone();
+                    self.change_dir(path);
+                }
+            }
+            Key::Tab if self.is_saving() => self.name_focused = !self.name_focused,
+            Key::Enter => return self.submit(terminal),
+            Key::Escape if !self.is_saving() && !self.input.is_empty() => {
+                self.input.clear();
+                self.refresh();
+            }
+            Key::Escape | Key::Interrupt => return Ok(Action::Cancel),
+            Key::Char(' ') if matches!(self.mode, Mode::Open { multiple: true }) => {
+                if let Some(entry) = self.entries.get(self.cursor)
+                    && !entry.is_dir
+                {
+                    match self.selected.iter().position(|path| *path == entry.path) {
+                        Some(i) => {
+                            self.selected.remove(i);
+                        }
+                        None => self.selected.push(entry.path.clone()),
+                    }
+                    self.cursor = (self.cursor + 1).min(last);
+                }
+            }
+            Key::Backspace => {
+                if self.input.pop().is_some() {
+                    self.name_focused = self.is_saving();
+                    self.refresh();
+                } else if !self.is_saving()
+                    && let Some(parent) = self.dir.parent()
+                {
+                    let parent = parent.to_path_buf();
+                    self.change_dir(parent);
+                }
+            }
+            Key::Char(c) => {
+                self.input.push(c);
+                self.status.clear();
+
+                if self.is_saving() {
+                    self.name_focused = true;
+                } else {
+                    self.cursor = 0;
+                    self.refresh();
+                }
+            }
+            _ => {}
+        }
+
+        Ok(Action::Continue)
+    }
+}
+
+pub fn run(
+    title: &str,
+    mode: Mode,
+    filter: &[BlockingPickFilesDialogFilter],
+    starting_directory: Option<&Path>,
+) -> Result<Vec<PathBuf>, BlockingDialogError> {
+    let dir = match starting_directory {
+        Some(dir) if dir.is_dir() => std::path::absolute(dir)?,
+        _ => env::current_dir().unwrap_or_else(|_| Path::new("/").to_path_buf()),
+    };
+
+    let mut browser = Browser::new(title, mode, filter, dir);
+    let mut terminal = Terminal::open()?;
+
+    loop {
+        browser.render(&mut terminal)?;
+
+        let key = terminal.read_key()?;
+        match browser.handle_key(key, &mut terminal)? {
+            Action::Continue => {}
+            Action::Cancel => return Ok(Vec::new()),
+            Action::Done(paths) => return Ok(paths),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::os_dialog::terminal::tests::open_terminal;
+    use crate::os_dialog::tty::{self, tests::Pty, tests::TempDir, tests::run_in_child};
+
+    const FILTER: &[BlockingPickFilesDialogFilter] = &[BlockingPickFilesDialogFilter {
+        name: "Text",
+        extensions: &["txt"],
+    }];
+
+    const FILES: &[&str] = &[
+        ".hidden.txt",
+        "a.txt",
+        "B.TXT",
+        "c.png",
+        "docs/",
+        "docs/d.txt",
+    ];
+
+    fn names<'a>(browser: &'a Browser) -> Vec<&'a str> {
+        browser
+            .entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    fn done(action: Action) -> Vec<PathBuf> {
+        match action {
+            Action::Done(paths) => paths,
+            Action::Continue => panic!("expected Done, got Continue"),
+            Action::Cancel => panic!("expected Done, got Cancel"),
+        }
+    }
+
+    #[test]
+    fn navigates() {
+        let dir = TempDir::new(FILES);
+        let (_master, mut terminal) = open_terminal();
+        let mode = Mode::Open { multiple: false };
+        let mut browser = Browser::new("Open", mode, FILTER, dir.path().to_path_buf());
+        assert_eq!(names(&browser), ["..", "docs", "a.txt", "B.TXT"]);
+
+        let mut press = |browser: &mut Browser, key| {
+            let action = browser.handle_key(key, &mut terminal).unwrap();
+            assert!(matches!(action, Action::Continue));
+            browser.cursor
+        };
+
+        assert_eq!(press(&mut browser, Key::Down), 1);
+        assert_eq!(press(&mut browser, Key::End), 3);
+        assert_eq!(press(&mut browser, Key::Down), 3);
+        assert_eq!(press(&mut browser, Key::Home), 0);
+        assert_eq!(press(&mut browser, Key::Up), 0);
+        assert_eq!(press(&mut browser, Key::PageDown), 3);
+        assert_eq!(press(&mut browser, Key::PageUp), 0);
+
+        press(&mut browser, Key::Down);
+        press(&mut browser, Key::Right);
+        assert_eq!(browser.dir, dir.path().join("docs"));
+        assert_eq!(names(&browser), ["..", "d.txt"]);
+
+        press(&mut browser, Key::Left);
+        assert_eq!(browser.dir, dir.path());
+        assert_eq!(browser.cursor, 0);
+
+        press(&mut browser, Key::Down);
+        press(&mut browser, Key::Enter);
+        assert_eq!(browser.dir, dir.path().join("docs"));
+
+        press(&mut browser, Key::Down);
+        let action = browser.handle_key(Key::Enter, &mut terminal).unwrap();
+        assert_eq!(done(action), [dir.path().join("docs/d.txt")]);
+
+        let action = browser.handle_key(Key::Escape, &mut terminal).unwrap();
+        assert!(matches!(action, Action::Cancel));
+    }
+
+    #[test]
+    fn refreshes_the_filter() {
+        let dir = TempDir::new(FILES);
+        let (_master, mut terminal) = open_terminal();
+        let mode = Mode::Open { multiple: false };
+        let mut browser = Browser::new("Open", mode, FILTER, dir.path().to_path_buf());
+        browser.cursor = 2;
+
+        browser.handle_key(Key::Char('b'), &mut terminal).unwrap();
+        assert_eq!(names(&browser), ["..", "B.TXT"]);
+        assert_eq!(browser.cursor, 0);
+
+        browser.handle_key(Key::Backspace, &mut terminal).unwrap();
+        assert_eq!(names(&browser), ["..", "docs", "a.txt", "B.TXT"]);
+
+        // Hidden files show up once the filter starts with a dot
+        browser.handle_key(Key::Char('.'), &mut terminal).unwrap();
+        assert_eq!(names(&browser), ["..", ".hidden.txt", "a.txt", "B.TXT"]);
+
+        let action = browser.handle_key(Key::Escape, &mut terminal).unwrap();
+        assert!(matches!(action, Action::Continue));
+        assert_eq!(names(&browser), ["..", "docs", "a.txt", "B.TXT"]);
+
+        // Backspace on an empty filter goes up
+        browser.handle_key(Key::Backspace, &mut terminal).unwrap();
+        assert_eq!(browser.dir, dir.path().parent().unwrap());
+    }
+
+    #[test]
+    fn toggles_marks() {
+        let dir = TempDir::new(FILES);
+        let (_master, mut terminal) = open_terminal();
+        let mode = Mode::Open { multiple: true };
+        let mut browser = Browser::new("Open", mode, FILTER, dir.path().to_path_buf());
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("B.TXT");
+
+        browser.cursor = 2;
+        browser.handle_key(Key::Char(' '), &mut terminal).unwrap();
+        assert_eq!(browser.selected, vec![a.clone()]);
+        assert_eq!(browser.cursor, 3);
+
+        browser.handle_key(Key::Char(' '), &mut terminal).unwrap();
+        assert_eq!(browser.selected, [a.clone(), b.clone()]);
+        assert_eq!(browser.cursor, 3);
+
+        browser.handle_key(Key::Char(' '), &mut terminal).unwrap();
+        assert_eq!(browser.selected, vec![a.clone()]);
+
+        // Directories can't be marked
+        browser.cursor = 1;
+        browser.handle_key(Key::Char(' '), &mut terminal).unwrap();
+        assert_eq!(browser.selected, vec![a.clone()]);
+        assert_eq!(browser.cursor, 1);
+
+        browser.cursor = 3;
+        let action = browser.handle_key(Key::Enter, &mut terminal).unwrap();
+        assert_eq!(done(action), [a]);
+    }
+
+    #[test]
+    fn picks_directories() {
+        let dir = TempDir::new(FILES);
+        let (_master, mut terminal) = open_terminal();
+        let mut browser = Browser::new("Open", Mode::Directory, &[], dir.path().to_path_buf());
+        assert_eq!(names(&browser), ["..", ".", "docs"]);
+
+        browser.cursor = 1;
+        let action = browser.handle_key(Key::Enter, &mut terminal).unwrap();
+        assert_eq!(done(action), [dir.path()]);
+    }
+
+    #[test]
+    fn saves_files() {
+        let dir = TempDir::new(FILES);
+        let (mut master, mut terminal) = open_terminal();
+        let mode = Mode::Save {
+            default_filename: Some("c.png"),
+        };
+        let mut browser = Browser::new("Save", mode, FILTER, dir.path().to_path_buf());
+        assert!(browser.name_focused);
+
+        browser.handle_key(Key::Enter, &mut terminal).unwrap();
+        assert_eq!(browser.status, "Not an allowed file type");
+
+        // Picking an existing file asks before overwriting it
+        browser.cursor = 2;
+        browser.handle_key(Key::Tab, &mut terminal).unwrap();
+        master.write_all(b"y").unwrap();
+        let action = browser.handle_key(Key::Enter, &mut terminal).unwrap();
+        assert_eq!(done(action), [dir.path().join("a.txt")]);
+
+        for key in [Key::Backspace; 5] {
+            browser.handle_key(key, &mut terminal).unwrap();
+        }
+        for c in "new.txt".chars() {
+            browser.handle_key(Key::Char(c), &mut terminal).unwrap();
+        }
+        let action = browser.handle_key(Key::Enter, &mut terminal).unwrap();
+        assert_eq!(done(action), [dir.path().join("new.txt")]);
+    }
+
+    #[test]
+    fn picks_files_in_a_terminal() {
+        if run_in_child(|| tty::pick_files("Open", true, FILTER, None)) {
+            return;
+        }
+
+        let path = concat!(module_path!(), "::picks_files_in_a_terminal");
+        let mut pty = Pty::spawn(path, "xterm", FILES);
+        pty.expect("Filter: ");
+        pty.send("\x1b[B\x1b[B  \r");
+
+        let paths = [pty.dir().join("a.txt"), pty.dir().join("B.TXT")];
+        assert_eq!(pty.result(), format!("Ok({paths:?})"));
+    }
+
+    #[test]
+    fn cancels_in_a_terminal() {
+        if run_in_child(|| tty::pick_directory("Open", None)) {
+            return;
+        }
+
+        let path = concat!(module_path!(), "::cancels_in_a_terminal");
+        let mut pty = Pty::spawn(path, "xterm", FILES);
+        pty.expect("(select this directory)");
+        pty.send("\x1b");
+        assert_eq!(pty.result(), "Ok(None)");
+    }
+}
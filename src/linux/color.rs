@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::dbus::Connection;
+use super::window_identifier::WindowIdentifier;
+use super::{kdialog, portal, tty, zenity};
+use crate::{BlockingColorDialog, BlockingDialogError, Rgba};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingColorDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Rgba>, BlockingDialogError> {
+        // The portal only offers an eyedropper that ignores every option, so it comes last
+        match backend::select(&[
+            Backend::Zenity,
+            Backend::Kdialog,
+            Backend::Tty,
+            Backend::Portal,
+        ])? {
+            Backend::Portal => {
+                let parent = WindowIdentifier::new(&self.window)?;
+                portal::pick_color(&mut Connection::session()?, parent.as_str())
+            }
+            Backend::Zenity => zenity::color(self.title, self.initial),
+            Backend::Kdialog => kdialog::color(self.title, self.initial),
+            Backend::Tty => tty::color(self.title, self.initial, self.alpha),
+        }
+    }
+}
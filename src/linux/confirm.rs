@@ -0,0 +1,36 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{kdialog, tty, zenity};
+use crate::{BlockingConfirmDialog, BlockingDialogError};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingConfirmDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<bool, BlockingDialogError> {
+        match backend::select(&[Backend::Zenity, Backend::Kdialog, Backend::Tty])? {
+            Backend::Zenity => zenity::confirm(
+                self.title,
+                self.message,
+                self.level,
+                self.accept_label,
+                self.cancel_label,
+            ),
+            Backend::Kdialog => kdialog::confirm(
+                self.title,
+                self.message,
+                self.level,
+                self.accept_label,
+                self.cancel_label,
+            ),
+            Backend::Tty => tty::confirm(
+                self.title,
+                self.message,
+                self.level,
+                self.accept_label,
+                self.cancel_label,
+            ),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
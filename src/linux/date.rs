@@ -0,0 +1,86 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{tty, zenity};
+use crate::{BlockingDateDialog, BlockingDialogError, BlockingDialogLevel, Date};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::mem::MaybeUninit;
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Today's date in the local time zone
+pub fn today() -> (i32, u32, u32) {
+    let tm = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm = MaybeUninit::<libc::tm>::zeroed();
+        libc::localtime_r(&now, tm.as_mut_ptr());
+        tm.assume_init()
+    };
+
+    (tm.tm_year + 1900, tm.tm_mon as u32 + 1, tm.tm_mday as u32)
+}
+
+/// Parses a `YYYY-MM-DD` date, `None` if it doesn't exist
+pub fn parse(s: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = s.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+
+    Some((year, month, day))
+}
+
+pub fn format(date: Date) -> String {
+    format!("{:04}-{:02}-{:02}", date.year, date.month, date.day)
+}
+
+/// Tells which dates can be picked, for when another one was
+pub fn describe_range(min: Option<Date>, max: Option<Date>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("Pick a date from {} to {}", format(min), format(max)),
+        (Some(min), None) => format!("Pick a date from {} on", format(min)),
+        (None, Some(max)) => format!("Pick a date up to {}", format(max)),
+        (None, None) => "Pick a date".to_string(),
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingDateDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Date>, BlockingDialogError> {
+        let initial = self.initial.unwrap_or_else(|| {
+            let (year, month, day) = today();
+            Date::new(year, month, day)
+        });
+        let mut initial = self.clamp(initial);
+
+        match backend::select(&[Backend::Zenity, Backend::Tty])? {
+            // The calendar has no bounds, so dates outside them are turned down after the fact
+            Backend::Zenity => loop {
+                let Some(date) = zenity::date(self.title, self.message, initial)? else {
+                    return Ok(None);
+                };
+
+                if self.clamp(date) == date {
+                    return Ok(Some(date));
+                }
+
+                let message = describe_range(self.min, self.max);
+                zenity::alert(self.title, &message, BlockingDialogLevel::Warning)?;
+                initial = self.clamp(date);
+            },
+            Backend::Tty => tty::date(self.title, self.message, initial, self.min, self.max),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
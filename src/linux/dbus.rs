@@ -0,0 +1,1028 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::BlockingDialogError;
+use std::collections::VecDeque;
+use std::env;
+use std::ffi::OsStr;
+use std::io::{Read, Write};
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::net::{SocketAddr, UnixStream};
+
+const METHOD_CALL: u8 = 1;
+const METHOD_RETURN: u8 = 2;
+const ERROR: u8 = 3;
+const SIGNAL: u8 = 4;
+
+const FIELD_PATH: u8 = 1;
+const FIELD_INTERFACE: u8 = 2;
+const FIELD_MEMBER: u8 = 3;
+const FIELD_ERROR_NAME: u8 = 4;
+const FIELD_REPLY_SERIAL: u8 = 5;
+const FIELD_DESTINATION: u8 = 6;
+const FIELD_SENDER: u8 = 7;
+const FIELD_SIGNATURE: u8 = 8;
+
+// The spec allows messages up to 128 MiB
+const MAX_MESSAGE_LEN: usize = 128 * 1024 * 1024;
+// Signatures are prefixed by a single length byte
+const MAX_SIGNATURE_LEN: usize = 255;
+// The spec allows 32 levels of arrays and 32 of structs, variants count too
+const MAX_DEPTH: usize = 64;
+
+fn protocol_error(msg: impl Into<String>) -> BlockingDialogError {
+    BlockingDialogError::DBus(msg.into())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Byte(u8),
+    Bool(bool),
+    I32(i32),
+    U32(u32),
+    Double(f64),
+    Str(String),
+    ObjectPath(String),
+    Signature(String),
+    Variant(Box<Value>),
+    /// Element signature and elements, the signature is needed for empty arrays
+    Array(String, Vec<Value>),
+    Struct(Vec<Value>),
+    DictEntry(Box<Value>, Box<Value>),
+}
+
+impl Value {
+    pub fn signature(&self) -> String {
+        match self {
+            Value::Byte(_) => "y".to_string(),
+            Value::Bool(_) => "b".to_string(),
+            Value::I32(_) => "i".to_string(),
+            Value::U32(_) => "u".to_string(),
+            Value::Double(_) => "d".to_string(),
+            Value::Str(_) => "s".to_string(),
+            Value::ObjectPath(_) => "o".to_string(),
+            Value::Signature(_) => "g".to_string(),
+            Value::Variant(_) => "v".to_string(),
+            Value::Array(elem, _) => format!("a{elem}"),
+            Value::Struct(fields) => {
+                let fields = fields.iter().map(Value::signature).collect::<String>();
+                format!("({fields})")
+            }
+            Value::DictEntry(key, value) => {
+                format!("{{{}{}}}", key.signature(), value.signature())
+            }
+        }
+    }
+
+    /// Builds an `a{sv}` dictionary
+    pub fn dict<'k>(entries: impl IntoIterator<Item = (&'k str, Value)>) -> Value {
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| {
+                Value::DictEntry(
+                    Box::new(Value::Str(key.to_string())),
+                    Box::new(Value::Variant(Box::new(value))),
+                )
+            })
+            .collect();
+
+        Value::Array("{sv}".to_string(), entries)
+    }
+
+    /// Builds a nul-terminated `ay`, the way portals pass file paths
+    pub fn bytes(bytes: &[u8]) -> Value {
+        let bytes = bytes.iter().chain([&0]).map(|b| Value::Byte(*b)).collect();
+
+        Value::Array("y".to_string(), bytes)
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            Value::U32(n) => Some(*n),
+            Value::Variant(v) => v.as_u32(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Double(n) => Some(*n),
+            Value::Variant(v) => v.as_f64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) | Value::ObjectPath(s) | Value::Signature(s) => Some(s),
+            Value::Variant(v) => v.as_str(),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(_, items) => Some(items),
+            Value::Variant(v) => v.as_array(),
+            _ => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&[Value]> {
+        match self {
+            Value::Struct(fields) => Some(fields),
+            Value::Variant(v) => v.as_struct(),
+            _ => None,
+        }
+    }
+
+    /// Looks up a key in an `a{sv}`-like dictionary
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_array()?.iter().find_map(|entry| match entry {
+            Value::DictEntry(k, v) if k.as_str() == Some(key) => match v.as_ref() {
+                Value::Variant(v) => Some(v.as_ref()),
+                v => Some(v),
+            },
+            _ => None,
+        })
+    }
+}
+
+fn alignment(sig: u8) -> usize {
+    match sig {
+        b'y' | b'g' | b'v' => 1,
+        b'n' | b'q' => 2,
+        b'b' | b'i' | b'u' | b's' | b'o' | b'a' | b'h' => 4,
+        _ => 8,
+    }
+}
+
+/// Splits the first complete type off a signature
+fn split_type(sig: &str) -> Result<(&str, &str), BlockingDialogError> {
+    let bytes = sig.as_bytes();
+
+    let len = match bytes.first() {
+        None => return Err(protocol_error("Empty signature")),
+        Some(b'a') => 1 + split_type(&sig[1..])?.0.len(),
+        Some(open @ (b'(' | b'{')) => {
+            let close = if *open == b'(' { b')' } else { b'}' };
+            let mut depth = 0;
+            let mut end = None;
+            for (i, c) in bytes.iter().enumerate() {
+                if *c == b'(' || *c == b'{' {
+                    depth += 1;
+                } else if *c == b')' || *c == b'}' {
+                    depth -= 1;
+                    if depth == 0 {
+                        if *c != close {
+                            return Err(protocol_error("Mismatched signature"));
+                        }
+                        end = Some(i + 1);
+                        break;
+                    }
+                }
+            }
+            end.ok_or_else(|| protocol_error("Unterminated signature"))?
+        }
+        Some(_) => 1,
+    };
+
+    Ok(sig.split_at(len))
+}
+
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn pad(&mut self, align: usize) {
+        while !self.buf.len().is_multiple_of(align) {
+            self.buf.push(0);
+        }
+    }
+
+    fn u32(&mut self, n: u32) {
+        self.pad(4);
+        self.buf.extend_from_slice(&n.to_le_bytes());
+    }
+
+    fn u64(&mut self, n: u64) {
+        self.pad(8);
+        self.buf.extend_from_slice(&n.to_le_bytes());
+    }
+
+    fn str(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.buf.extend_from_slice(s.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn signature(&mut self, s: &str) -> Result<(), BlockingDialogError> {
+        if s.len() > MAX_SIGNATURE_LEN {
+            return Err(protocol_error("Signature too long"));
+        }
+
+        self.buf.push(s.len() as u8);
+        self.buf.extend_from_slice(s.as_bytes());
+        self.buf.push(0);
+        Ok(())
+    }
+
+    fn value(&mut self, value: &Value) -> Result<(), BlockingDialogError> {
+        match value {
+            Value::Byte(b) => self.buf.push(*b),
+            Value::Bool(b) => self.u32(u32::from(*b)),
+            Value::I32(n) => self.u32(*n as u32),
+            Value::U32(n) => self.u32(*n),
+            Value::Double(n) => self.u64(n.to_bits()),
+            Value::Str(s) | Value::ObjectPath(s) => self.str(s),
+            Value::Signature(s) => self.signature(s)?,
+            Value::Variant(v) => {
+                self.signature(&v.signature())?;
+                self.value(v)?;
+            }
+            Value::Array(elem, items) => {
+                self.u32(0);
+                let len_pos = self.buf.len() - 4;
+                self.pad(alignment(elem.as_bytes()[0]));
+                let start = self.buf.len();
+                for item in items {
+                    self.value(item)?;
+                }
+                let len = (self.buf.len() - start) as u32;
+                self.buf[len_pos..len_pos + 4].copy_from_slice(&len.to_le_bytes());
+            }
+            Value::Struct(fields) => {
+                self.pad(8);
+                for field in fields {
+                    self.value(field)?;
+                }
+            }
+            Value::DictEntry(key, value) => {
+                self.pad(8);
+                self.value(key)?;
+                self.value(value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn align(&mut self, align: usize) -> Result<(), BlockingDialogError> {
+        let pos = self.pos.next_multiple_of(align);
+        if pos > self.buf.len() {
+            return Err(protocol_error("Truncated message"));
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BlockingDialogError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.buf.len())
+            .ok_or_else(|| protocol_error("Truncated message"))?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, BlockingDialogError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, BlockingDialogError> {
+        self.align(4)?;
+        let bytes: [u8; 4] = self.take(4)?.try_into().unwrap_or_default();
+        if self.big_endian {
+            Ok(u32::from_be_bytes(bytes))
+        } else {
+            Ok(u32::from_le_bytes(bytes))
+        }
+    }
+
+    fn u64(&mut self) -> Result<u64, BlockingDialogError> {
+        self.align(8)?;
+        let bytes: [u8; 8] = self.take(8)?.try_into().unwrap_or_default();
+        if self.big_endian {
+            Ok(u64::from_be_bytes(bytes))
+        } else {
+            Ok(u64::from_le_bytes(bytes))
+        }
+    }
+
+    fn string(&mut self, len: usize) -> Result<String, BlockingDialogError> {
+        let bytes = self.take(len)?;
+        let _nul = self.u8()?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| protocol_error("Invalid UTF-8 string"))
+    }
+
+    fn value(&mut self, sig: &str) -> Result<Value, BlockingDialogError> {
+        self.nested_value(sig, 0)
+    }
+
+    /// Reads a value inside `depth` containers, so a hostile peer can't exhaust the stack
+    fn nested_value(&mut self, sig: &str, depth: usize) -> Result<Value, BlockingDialogError> {
+        if depth > MAX_DEPTH {
+            return Err(protocol_error("Message nested too deeply"));
+        }
+
+        let Some(code) = sig.as_bytes().first() else {
+            return Err(protocol_error("Empty signature"));
+        };
+
+        let value = match code {
+            b'y' => Value::Byte(self.u8()?),
+            b'b' => Value::Bool(self.u32()? != 0),
+            b'i' => Value::I32(self.u32()? as i32),
+            b'u' => Value::U32(self.u32()?),
+            b'd' => Value::Double(f64::from_bits(self.u64()?)),
+            b's' => {
+                let len = self.u32()? as usize;
+                Value::Str(self.string(len)?)
+            }
+            b'o' => {
+                let len = self.u32()? as usize;
+                Value::ObjectPath(self.string(len)?)
+            }
+            b'g' => {
+                let len = self.u8()? as usize;
+                Value::Signature(self.string(len)?)
+            }
+            b'v' => {
+                let len = self.u8()? as usize;
+                let inner_sig = self.string(len)?;
+                let (inner, rest) = split_type(&inner_sig)?;
+                if !rest.is_empty() {
+                    return Err(protocol_error("Variant with more than one type"));
+                }
+                Value::Variant(Box::new(self.nested_value(inner, depth + 1)?))
+            }
+            b'a' => {
+                let elem = &sig[1..];
+                let Some(elem_code) = elem.as_bytes().first() else {
+                    return Err(protocol_error("Array without an element type"));
+                };
+                let len = self.u32()? as usize;
+                self.align(alignment(*elem_code))?;
+                let end = self.pos + len;
+                if end > self.buf.len() {
+                    return Err(protocol_error("Truncated array"));
+                }
+                let mut items = Vec::new();
+                while self.pos < end {
+                    items.push(self.nested_value(elem, depth + 1)?);
+                }
+                Value::Array(elem.to_string(), items)
+            }
+            b'(' => {
+                // An empty struct would take no bytes, and an array of them never ends
+                let mut rest = &sig[1..sig.len() - 1];
+                if rest.is_empty() {
+                    return Err(protocol_error("Empty struct"));
+                }
+                self.align(8)?;
+                let mut fields = Vec::new();
+                while !rest.is_empty() {
+                    let (field, tail) = split_type(rest)?;
+                    fields.push(self.nested_value(field, depth + 1)?);
+                    rest = tail;
+                }
+                Value::Struct(fields)
+            }
+            b'{' => {
+                let (key, rest) = split_type(&sig[1..sig.len() - 1])?;
+                let (value, rest) = split_type(rest)?;
+                if !rest.is_empty() {
+                    return Err(protocol_error("Dict entry with more than two types"));
+                }
+                self.align(8)?;
+                let key = self.nested_value(key, depth + 1)?;
+                let value = self.nested_value(value, depth + 1)?;
+                Value::DictEntry(Box::new(key), Box::new(value))
+            }
+            c => return Err(protocol_error(format!("Unsupported type '{}'", *c as char))),
+        };
+
+        Ok(value)
+    }
+
+    fn values(&mut self, mut sig: &str) -> Result<Vec<Value>, BlockingDialogError> {
+        let mut values = Vec::new();
+        while !sig.is_empty() {
+            let (first, rest) = split_type(sig)?;
+            values.push(self.value(first)?);
+            sig = rest;
+        }
+        Ok(values)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Message {
+    pub msg_type: u8,
+    pub serial: u32,
+    pub reply_serial: Option<u32>,
+    pub path: Option<String>,
+    pub interface: Option<String>,
+    pub member: Option<String>,
+    pub error_name: Option<String>,
+    pub destination: Option<String>,
+    pub sender: Option<String>,
+    pub body: Vec<Value>,
+}
+
+impl Message {
+    pub fn method_call(destination: &str, path: &str, interface: &str, member: &str) -> Self {
+        Message {
+            msg_type: METHOD_CALL,
+            path: Some(path.to_string()),
+            interface: Some(interface.to_string()),
+            member: Some(member.to_string()),
+            destination: Some(destination.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(test)]
+    pub fn method_return(call: &Message) -> Self {
+        Message {
+            msg_type: METHOD_RETURN,
+            reply_serial: Some(call.serial),
+            destination: call.sender.clone(),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(test)]
+    pub fn signal(path: &str, interface: &str, member: &str) -> Self {
+        Message {
+            msg_type: SIGNAL,
+            path: Some(path.to_string()),
+            interface: Some(interface.to_string()),
+            member: Some(member.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_body(mut self, body: Vec<Value>) -> Self {
+        self.body = body;
+        self
+    }
+
+    pub fn marshal(&self) -> Result<Vec<u8>, BlockingDialogError> {
+        let signature = self.body.iter().map(Value::signature).collect::<String>();
+
+        let mut body = Writer { buf: Vec::new() };
+        for value in &self.body {
+            body.value(value)?;
+        }
+
+        let mut fields = Vec::new();
+        let mut field = |code: u8, value: Value| {
+            fields.push(Value::Struct(vec![
+                Value::Byte(code),
+                Value::Variant(Box::new(value)),
+            ]));
+        };
+
+        if let Some(path) = &self.path {
+            field(FIELD_PATH, Value::ObjectPath(path.clone()));
+        }
+        if let Some(interface) = &self.interface {
+            field(FIELD_INTERFACE, Value::Str(interface.clone()));
+        }
+        if let Some(member) = &self.member {
+            field(FIELD_MEMBER, Value::Str(member.clone()));
+        }
+        if let Some(error_name) = &self.error_name {
+            field(FIELD_ERROR_NAME, Value::Str(error_name.clone()));
+        }
+        if let Some(reply_serial) = self.reply_serial {
+            field(FIELD_REPLY_SERIAL, Value::U32(reply_serial));
+        }
+        if let Some(destination) = &self.destination {
+            field(FIELD_DESTINATION, Value::Str(destination.clone()));
+        }
+        if let Some(sender) = &self.sender {
+            field(FIELD_SENDER, Value::Str(sender.clone()));
+        }
+        if !signature.is_empty() {
+            field(FIELD_SIGNATURE, Value::Signature(signature));
+        }
+
+        let mut header = Writer { buf: Vec::new() };
+        header.buf.extend_from_slice(&[b'l', self.msg_type, 0, 1]);
+        header.u32(body.buf.len() as u32);
+        header.u32(self.serial);
+        header.value(&Value::Array("(yv)".to_string(), fields))?;
+        header.pad(8);
+        header.buf.extend_from_slice(&body.buf);
+
+        Ok(header.buf)
+    }
+
+    pub fn unmarshal(buf: &[u8]) -> Result<Self, BlockingDialogError> {
+        let big_endian = match buf.first() {
+            Some(b'l') => false,
+            Some(b'B') => true,
+            _ => return Err(protocol_error("Invalid endianness")),
+        };
+
+        let mut reader = Reader {
+            buf,
+            pos: 1,
+            big_endian,
+        };
+
+        let msg_type = reader.u8()?;
+        let _flags = reader.u8()?;
+        let _version = reader.u8()?;
+        let _body_len = reader.u32()?;
+        let serial = reader.u32()?;
+        let fields = reader.value("a(yv)")?;
+
+        let mut msg = Message {
+            msg_type,
+            serial,
+            ..Default::default()
+        };
+
+        let mut signature = String::new();
+        for field in fields.as_array().unwrap_or_default() {
+            let Value::Struct(field) = field else {
+                continue;
+            };
+            let (Some(Value::Byte(code)), Some(value)) = (field.first(), field.get(1)) else {
+                continue;
+            };
+
+            let string = value.as_str().map(str::to_string);
+            match *code {
+                FIELD_PATH => msg.path = string,
+                FIELD_INTERFACE => msg.interface = string,
+                FIELD_MEMBER => msg.member = string,
+                FIELD_ERROR_NAME => msg.error_name = string,
+                FIELD_REPLY_SERIAL => msg.reply_serial = value.as_u32(),
+                FIELD_DESTINATION => msg.destination = string,
+                FIELD_SENDER => msg.sender = string,
+                FIELD_SIGNATURE => signature = string.unwrap_or_default(),
+                _ => {}
+            }
+        }
+
+        reader.align(8)?;
+        msg.body = reader.values(&signature)?;
+
+        Ok(msg)
+    }
+}
+
+fn unescape_address_value(value: &str) -> Vec<u8> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = value.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+fn connect_address(address: &str) -> Option<UnixStream> {
+    let params = address.strip_prefix("unix:")?;
+
+    for param in params.split(',') {
+        let Some((key, value)) = param.split_once('=') else {
+            continue;
+        };
+
+        let value = unescape_address_value(value);
+        let addr = match key {
+            "path" => SocketAddr::from_pathname(OsStr::from_bytes(&value)),
+            "abstract" => SocketAddr::from_abstract_name(&value),
+            _ => continue,
+        };
+
+        if let Ok(addr) = addr
+            && let Ok(stream) = UnixStream::connect_addr(&addr)
+        {
+            return Some(stream);
+        }
+    }
+
+    None
+}
+
+pub struct Connection {
+    stream: UnixStream,
+    serial: u32,
+    unique_name: String,
+    pending: VecDeque<Message>,
+}
+
+impl Connection {
+    pub fn session() -> Result<Self, BlockingDialogError> {
+        let address = match env::var("DBUS_SESSION_BUS_ADDRESS") {
+            Ok(address) => address,
+            Err(_) => {
+                let runtime_dir = env::var("XDG_RUNTIME_DIR")
+                    .map_err(|_| protocol_error("No session bus address"))?;
+                format!("unix:path={runtime_dir}/bus")
+            }
+        };
+
+        let stream = address
+            .split(';')
+            .find_map(connect_address)
+            .ok_or_else(|| protocol_error(format!("Can't connect to {address}")))?;
+
+        Self::from_stream(stream)
+    }
+
+    pub fn from_stream(mut stream: UnixStream) -> Result<Self, BlockingDialogError> {
+        let uid = unsafe { libc::getuid() };
+        let hex_uid = uid
+            .to_string()
+            .bytes()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+
+        stream.write_all(b"\0")?;
+        stream.write_all(format!("AUTH EXTERNAL {hex_uid}\r\n").as_bytes())?;
+
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+        while !line.ends_with(b"\r\n") {
+            stream.read_exact(&mut byte)?;
+            line.push(byte[0]);
+        }
+
+        if !line.starts_with(b"OK ") {
+            return Err(protocol_error("Authentication rejected"));
+        }
+
+        stream.write_all(b"BEGIN\r\n")?;
+
+        let mut conn = Connection {
+            stream,
+            serial: 0,
+            unique_name: String::new(),
+            pending: VecDeque::new(),
+        };
+
+        let reply = conn.call_method(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "Hello",
+            Vec::new(),
+        )?;
+
+        conn.unique_name = reply
+            .body
+            .first()
+            .and_then(Value::as_str)
+            .ok_or_else(|| protocol_error("Invalid Hello reply"))?
+            .to_string();
+
+        Ok(conn)
+    }
+
+    pub fn unique_name(&self) -> &str {
+        &self.unique_name
+    }
+
+    pub fn send(&mut self, mut msg: Message) -> Result<u32, BlockingDialogError> {
+        self.serial += 1;
+        msg.serial = self.serial;
+        self.stream.write_all(&msg.marshal()?)?;
+        Ok(msg.serial)
+    }
+
+    fn read_message(&mut self) -> Result<Message, BlockingDialogError> {
+        let mut fixed = [0u8; 16];
+        self.stream.read_exact(&mut fixed)?;
+
+        let read_u32 = |bytes: &[u8]| {
+            let bytes: [u8; 4] = bytes.try_into().unwrap_or_default();
+            if fixed[0] == b'B' {
+                u32::from_be_bytes(bytes)
+            } else {
+                u32::from_le_bytes(bytes)
+            }
+        };
+
+        let body_len = read_u32(&fixed[4..8]) as usize;
+        let fields_len = read_u32(&fixed[12..16]) as usize;
+        let total = (16 + fields_len).next_multiple_of(8) + body_len;
+
+        if total > MAX_MESSAGE_LEN {
+            return Err(protocol_error("Message too long"));
+        }
+
+        let mut buf = vec![0u8; total];
+        buf[..16].copy_from_slice(&fixed);
+        self.stream.read_exact(&mut buf[16..])?;
+
+        Message::unmarshal(&buf)
+    }
+
+    /// Takes the next message, including those set aside while waiting for a reply
+    #[cfg(test)]
+    pub fn receive(&mut self) -> Result<Message, BlockingDialogError> {
+        match self.pending.pop_front() {
+            Some(msg) => Ok(msg),
+            None => self.read_message(),
+        }
+    }
+
+    pub fn call_method(
+        &mut self,
+        destination: &str,
+        path: &str,
+        interface: &str,
+        member: &str,
+        body: Vec<Value>,
+    ) -> Result<Message, BlockingDialogError> {
+        let msg = Message::method_call(destination, path, interface, member).with_body(body);
+        let serial = self.send(msg)?;
+
+        loop {
+            let msg = self.read_message()?;
+
+            if msg.reply_serial != Some(serial) {
+                self.pending.push_back(msg);
+                continue;
+            }
+
+            return match msg.msg_type {
+                METHOD_RETURN => Ok(msg),
+                ERROR => {
+                    let name = msg.error_name.unwrap_or_default();
+                    let detail = msg.body.first().and_then(Value::as_str).unwrap_or_default();
+                    Err(protocol_error(format!("{name}: {detail}")))
+                }
+                _ => Err(protocol_error("Unexpected reply type")),
+            };
+        }
+    }
+
+    pub fn add_match(&mut self, rule: &str) -> Result<(), BlockingDialogError> {
+        let _ = self.call_method(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "AddMatch",
+            vec![Value::Str(rule.to_string())],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn wait_for_signal(
+        &mut self,
+        path: &str,
+        interface: &str,
+        member: &str,
+    ) -> Result<Message, BlockingDialogError> {
+        let matches = |msg: &Message| {
+            msg.msg_type == SIGNAL
+                && msg.path.as_deref() == Some(path)
+                && msg.interface.as_deref() == Some(interface)
+                && msg.member.as_deref() == Some(member)
+        };
+
+        if let Some(i) = self.pending.iter().position(matches)
+            && let Some(msg) = self.pending.remove(i)
+        {
+            return Ok(msg);
+        }
+
+        loop {
+            let msg = self.read_message()?;
+            if matches(&msg) {
+                return Ok(msg);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use std::time::Duration;
+
+    /// A private session bus, so that tests don't need a desktop
+    pub struct TestBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl TestBus {
+        /// Starts a private bus, the tests using it are ignored by default
+        pub fn start() -> Self {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .expect("start dbus-daemon");
+
+            let mut address = String::new();
+            let stdout = daemon.stdout.take().expect("dbus-daemon's stdout");
+            BufReader::new(stdout)
+                .read_line(&mut address)
+                .expect("read the bus address");
+
+            TestBus {
+                daemon,
+                address: address.trim().to_string(),
+            }
+        }
+
+        /// Connects with a timeout, so that a test fails rather than hangs
+        pub fn connect(&self) -> Connection {
+            let stream = self
+                .address
+                .split(';')
+                .find_map(connect_address)
+                .expect("connect to the test bus");
+            stream
+                .set_read_timeout(Some(Duration::from_secs(10)))
+                .expect("set a read timeout");
+
+            Connection::from_stream(stream).expect("authenticate with the test bus")
+        }
+    }
+
+    impl Drop for TestBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    fn round_trip(body: Vec<Value>) -> Message {
+        let msg = Message::method_call("org.example.Dest", "/org/example", "org.example.I", "M")
+            .with_body(body);
+        Message::unmarshal(&msg.marshal().expect("marshal")).expect("unmarshal")
+    }
+
+    fn read(buf: &[u8], sig: &str) -> Result<Value, BlockingDialogError> {
+        let mut reader = Reader {
+            buf,
+            pos: 0,
+            big_endian: false,
+        };
+        reader.value(sig)
+    }
+
+    #[test]
+    fn round_trips_header() {
+        let msg = round_trip(Vec::new());
+
+        assert_eq!(msg.msg_type, METHOD_CALL);
+        assert_eq!(msg.destination.as_deref(), Some("org.example.Dest"));
+        assert_eq!(msg.path.as_deref(), Some("/org/example"));
+        assert_eq!(msg.interface.as_deref(), Some("org.example.I"));
+        assert_eq!(msg.member.as_deref(), Some("M"));
+        assert!(msg.body.is_empty());
+    }
+
+    #[test]
+    fn round_trips_strings_and_paths() {
+        let body = vec![
+            Value::Str("héllo".to_string()),
+            Value::Str(String::new()),
+            Value::ObjectPath("/org/freedesktop/portal/desktop".to_string()),
+        ];
+
+        assert_eq!(round_trip(body.clone()).body, body);
+    }
+
+    #[test]
+    fn round_trips_dict() {
+        let body = vec![Value::dict([
+            ("modal", Value::Bool(true)),
+            ("handle_token", Value::Str("token".to_string())),
+            ("response", Value::U32(7)),
+            ("color", Value::Struct(vec![Value::Double(0.5); 3])),
+        ])];
+
+        let msg = round_trip(body.clone());
+        assert_eq!(msg.body, body);
+        assert_eq!(msg.body[0].get("response").and_then(Value::as_u32), Some(7));
+        assert_eq!(msg.body[0].get("missing"), None);
+    }
+
+    #[test]
+    fn round_trips_byte_arrays() {
+        let body = vec![
+            Value::Array(
+                "ay".to_string(),
+                vec![
+                    Value::bytes(b"/tmp"),
+                    Value::bytes(b""),
+                    Value::bytes(b"\xff"),
+                ],
+            ),
+            Value::Array("ay".to_string(), Vec::new()),
+        ];
+
+        assert_eq!(round_trip(body.clone()).body, body);
+    }
+
+    #[test]
+    fn round_trips_filters() {
+        let pattern = |glob: &str| Value::Struct(vec![Value::U32(0), Value::Str(glob.to_string())]);
+        let body = vec![Value::Array(
+            "(sa(us))".to_string(),
+            vec![
+                Value::Struct(vec![
+                    Value::Str("Images".to_string()),
+                    Value::Array("(us)".to_string(), vec![pattern("*.png"), pattern("*.jpg")]),
+                ]),
+                Value::Struct(vec![
+                    Value::Str("None".to_string()),
+                    Value::Array("(us)".to_string(), Vec::new()),
+                ]),
+            ],
+        )];
+
+        assert_eq!(round_trip(body.clone()).body, body);
+    }
+
+    #[test]
+    fn rejects_malformed_signatures() {
+        let buf = [8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        assert!(read(&buf, "").is_err());
+        assert!(read(&buf, "()").is_err());
+        assert!(read(&buf, "a()").is_err());
+        assert!(read(&buf, "{s}").is_err());
+        assert!(read(&buf, "{sss}").is_err());
+        assert!(read(&buf, "a{s}").is_err());
+    }
+
+    #[test]
+    fn rejects_deep_nesting() {
+        // Variants holding variants, each a signature "v" then the next one, down to a byte
+        let nested = |depth: usize| [[1, b'v', 0].repeat(depth - 1), vec![1, b'y', 0, 7]].concat();
+
+        assert!(read(&nested(MAX_DEPTH), "v").is_ok());
+        assert!(read(&nested(MAX_DEPTH + 1), "v").is_err());
+        assert!(read(&nested(100_000), "v").is_err());
+    }
+
+    #[test]
+    fn rejects_long_signatures() {
+        let msg = Message::method_call("org.example.Dest", "/org/example", "org.example.I", "M")
+            .with_body(vec![Value::Signature("y".repeat(256))]);
+
+        assert!(msg.marshal().is_err());
+    }
+
+    #[test]
+    #[ignore = "needs dbus-daemon, run with --ignored"]
+    fn says_hello_to_a_bus() {
+        let bus = TestBus::start();
+
+        let mut conn = bus.connect();
+        assert!(conn.unique_name().starts_with(':'));
+
+        let reply = conn
+            .call_method(
+                "org.freedesktop.DBus",
+                "/org/freedesktop/DBus",
+                "org.freedesktop.DBus",
+                "GetNameOwner",
+                vec![Value::Str(conn.unique_name().to_string())],
+            )
+            .expect("GetNameOwner");
+        assert_eq!(
+            reply.body.first().and_then(Value::as_str),
+            Some(conn.unique_name())
+        );
+    }
+}
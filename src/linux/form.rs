@@ -0,0 +1,27 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{tty, zenity};
+use crate::{BlockingDialogError, BlockingFormDialog, BlockingFormDialogValue};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::collections::HashMap;
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingFormDialog<'a, W> {
+    pub(crate) fn show_native(
+        &self,
+    ) -> Result<Option<HashMap<String, BlockingFormDialogValue>>, BlockingDialogError> {
+        // kdialog has no multi-field form
+        let backends: &[Backend] = if zenity::can_show_form(self.fields) {
+            &[Backend::Zenity, Backend::Tty]
+        } else {
+            &[Backend::Tty]
+        };
+
+        match backend::select(backends)? {
+            Backend::Zenity => zenity::form(self.title, self.message, self.fields),
+            Backend::Tty => tty::form(self.title, self.message, self.fields),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
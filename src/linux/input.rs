@@ -0,0 +1,23 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{kdialog, tty, zenity};
+use crate::{BlockingDialogError, BlockingInputDialog};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingInputDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<String>, BlockingDialogError> {
+        match backend::select(&[Backend::Zenity, Backend::Kdialog, Backend::Tty])? {
+            Backend::Zenity => zenity::input(self.title, self.message, self.default_text),
+            Backend::Kdialog => kdialog::input(self.title, self.message, self.default_text),
+            Backend::Tty => tty::input(
+                self.title,
+                self.message,
+                self.default_text,
+                self.placeholder,
+            ),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
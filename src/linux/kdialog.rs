@@ -0,0 +1,639 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::rgba;
+use super::subprocess::{exited, parse_indices, parse_paths, parse_secret, run, run_with_code};
+use crate::{
+    BlockingDialogError, BlockingDialogLevel, BlockingListDialogMode,
+    BlockingPickFilesDialogFilter, BlockingQuestionDialogResponse, Credentials, Rgba, Secret,
+};
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const PROGRAM: &str = "kdialog";
+
+fn get_filter(filter: &[BlockingPickFilesDialogFilter]) -> String {
+    filter
+        .iter()
+        .map(|entry| {
+            let patterns = entry
+                .extensions
+                .iter()
+                .map(|ext| format!("*.{ext}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            format!("{} ({})", entry.name, patterns)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn alert(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+) -> Result<(), BlockingDialogError> {
+    let kind = match level {
+        BlockingDialogLevel::Info => "--msgbox",
+        BlockingDialogLevel::Warning => "--sorry",
+        BlockingDialogLevel::Error => "--error",
+    };
+
+    let args = ["--title", title, kind, message];
+    let _ = run(PROGRAM, args)?;
+
+    Ok(())
+}
+
+pub fn confirm(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+    accept_label: Option<&str>,
+    cancel_label: Option<&str>,
+) -> Result<bool, BlockingDialogError> {
+    let (kind, accept_option, cancel_option) = match level {
+        BlockingDialogLevel::Info => ("--yesno", "--yes-label", "--no-label"),
+        BlockingDialogLevel::Warning | BlockingDialogLevel::Error => (
+            "--warningcontinuecancel",
+            "--continue-label",
+            "--cancel-label",
+        ),
+    };
+
+    let mut args = vec!["--title", title, kind, message];
+
+    if let Some(accept_label) = accept_label {
+        args.extend([accept_option, accept_label]);
+    }
+
+    if let Some(cancel_label) = cancel_label {
+        args.extend([cancel_option, cancel_label]);
+    }
+
+    // --warningcontinuecancel exits with 2 on cancel rather than 1
+    match run_with_code(PROGRAM, args)? {
+        (0, _) => Ok(true),
+        (1 | 2, _) => Ok(false),
+        (code, _) => Err(exited(PROGRAM, code)),
+    }
+}
+
+pub fn question(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+    let kind = match level {
+        BlockingDialogLevel::Info => "--yesnocancel",
+        BlockingDialogLevel::Warning | BlockingDialogLevel::Error => "--warningyesnocancel",
+    };
+
+    let args = ["--title", title, kind, message];
+
+    // Cancel and closing the window both exit with 2
+    let response = match run_with_code(PROGRAM, args)? {
+        (0, _) => BlockingQuestionDialogResponse::Yes,
+        (1, _) => BlockingQuestionDialogResponse::No,
+        (2, _) => BlockingQuestionDialogResponse::Cancel,
+        (code, _) => return Err(exited(PROGRAM, code)),
+    };
+
+    Ok(response)
+}
+
+pub fn message(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+    buttons: &[&str],
+    default_button: usize,
+    cancel_button: usize,
+) -> Result<usize, BlockingDialogError> {
+    let warning = level != BlockingDialogLevel::Info;
+    let mut args = vec!["--title".to_string(), title.to_string()];
+
+    // kdialog only has fixed layouts of up to three buttons, which focus the
+    // first one and answer the window close button like the last. Any other
+    // count or default and cancel buttons are shown as a menu.
+    let fixed_layout = default_button == 0 && cancel_button == buttons.len().saturating_sub(1);
+
+    match buttons {
+        [_] => {
+            alert(title, message, level)?;
+            return Ok(0);
+        }
+        [yes, no] if fixed_layout => {
+            let kind = if warning { "--warningyesno" } else { "--yesno" };
+            args.extend([kind.to_string(), message.to_string()]);
+            args.extend(["--yes-label".to_string(), yes.to_string()]);
+            args.extend(["--no-label".to_string(), no.to_string()]);
+        }
+        [yes, no, cancel] if fixed_layout => {
+            let kind = if warning {
+                "--warningyesnocancel"
+            } else {
+                "--yesnocancel"
+            };
+            args.extend([kind.to_string(), message.to_string()]);
+            args.extend(["--yes-label".to_string(), yes.to_string()]);
+            args.extend(["--no-label".to_string(), no.to_string()]);
+            args.extend(["--cancel-label".to_string(), cancel.to_string()]);
+        }
+        _ => {
+            args.extend(["--menu".to_string(), message.to_string()]);
+
+            for (i, button) in buttons.iter().enumerate() {
+                args.extend([i.to_string(), button.to_string()]);
+            }
+
+            if let Some(default) = buttons.get(default_button) {
+                args.extend(["--default".to_string(), default.to_string()]);
+            }
+
+            let output = run(PROGRAM, args)?;
+            let index = output
+                .and_then(|stdout| String::from_utf8(stdout).ok())
+                .and_then(|tag| tag.parse().ok())
+                .filter(|i| *i < buttons.len())
+                .unwrap_or(cancel_button);
+
+            return Ok(index);
+        }
+    }
+
+    match run_with_code(PROGRAM, args)? {
+        (code @ 0..=2, _) => Ok(code as usize),
+        (code, _) => Err(exited(PROGRAM, code)),
+    }
+}
+
+pub fn input(
+    title: &str,
+    message: &str,
+    default_text: Option<&str>,
+) -> Result<Option<String>, BlockingDialogError> {
+    let mut args = vec!["--title", title, "--inputbox", message];
+
+    if let Some(default_text) = default_text {
+        args.push(default_text);
+    }
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(|stdout| String::from_utf8_lossy(&stdout).into_owned()))
+}
+
+pub fn password(title: &str, message: &str) -> Result<Option<Secret>, BlockingDialogError> {
+    let args = ["--title", title, "--password", message];
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_secret))
+}
+
+/// kdialog has no two-field form, so it asks for the username first
+pub fn login(
+    title: &str,
+    message: &str,
+    username: Option<&str>,
+) -> Result<Option<Credentials>, BlockingDialogError> {
+    let mut args = vec!["--title", title, "--inputbox", message];
+    args.extend(username);
+
+    let Some(username) = run(PROGRAM, args)? else {
+        return Ok(None);
+    };
+
+    let username = String::from_utf8_lossy(&username).into_owned();
+
+    let Some(password) = password(title, &format!("Password for {username}:"))? else {
+        return Ok(None);
+    };
+
+    Ok(Some(Credentials { username, password }))
+}
+
+/// kdialog lists have a single column, so the cells of each row are joined
+pub fn list(
+    title: &str,
+    message: &str,
+    rows: &[&[&str]],
+    selected: &[usize],
+    mode: BlockingListDialogMode,
+) -> Result<Vec<usize>, BlockingDialogError> {
+    let mut args = vec!["--title".to_string(), title.to_string()];
+
+    let kind = match mode {
+        BlockingListDialogMode::Radio => "--radiolist",
+        BlockingListDialogMode::Checklist => "--checklist",
+        BlockingListDialogMode::Plain => "--menu",
+    };
+    args.extend([kind.to_string(), message.to_string()]);
+
+    for (i, row) in rows.iter().enumerate() {
+        args.extend([i.to_string(), row.join(" - ")]);
+
+        if mode != BlockingListDialogMode::Plain {
+            let state = if selected.contains(&i) { "on" } else { "off" };
+            args.push(state.to_string());
+        }
+    }
+
+    match mode {
+        BlockingListDialogMode::Checklist => args.push("--separate-output".to_string()),
+        BlockingListDialogMode::Plain => {
+            if let Some(row) = selected.first().and_then(|i| rows.get(*i)) {
+                args.extend(["--default".to_string(), row.join(" - ")]);
+            }
+        }
+        BlockingListDialogMode::Radio => {}
+    }
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_indices).unwrap_or_default())
+}
+
+/// kdialog's color dialog has no transparency
+pub fn color(title: &str, initial: Option<Rgba>) -> Result<Option<Rgba>, BlockingDialogError> {
+    let mut args = vec![
+        "--title".to_string(),
+        title.to_string(),
+        "--getcolor".to_string(),
+    ];
+
+    if let Some(initial) = initial {
+        args.extend(["--default".to_string(), rgba::to_hex(initial, false)]);
+    }
+
+    let Some(output) = run(PROGRAM, args)? else {
+        return Ok(None);
+    };
+
+    let output = String::from_utf8_lossy(&output);
+    rgba::parse(&output).map(Some).ok_or_else(|| {
+        BlockingDialogError::Io(io::Error::other(format!(
+            "{PROGRAM} printed an invalid color: {output}"
+        )))
+    })
+}
+
+/// The start location kdialog expects, a directory optionally followed by a file name
+fn get_start_path(starting_directory: Option<&Path>, default_filename: Option<&str>) -> OsString {
+    match (starting_directory, default_filename) {
+        (Some(dir), Some(name)) => dir.join(name).into_os_string(),
+        (Some(dir), None) => dir.as_os_str().to_os_string(),
+        (None, Some(name)) => name.into(),
+        (None, None) => ".".into(),
+    }
+}
+
+pub fn pick_files(
+    title: &str,
+    multiple: bool,
+    filter: &[BlockingPickFilesDialogFilter],
+    starting_directory: Option<&Path>,
+) -> Result<Vec<PathBuf>, BlockingDialogError> {
+    let mut args: Vec<OsString> = vec!["--title".into(), title.into()];
+
+    if multiple {
+        args.push("--multiple".into());
+        args.push("--separate-output".into());
+    }
+
+    args.push("--getopenfilename".into());
+    args.push(get_start_path(starting_directory, None));
+
+    if !filter.is_empty() {
+        args.push(get_filter(filter).into());
+    }
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_paths).unwrap_or_default())
+}
+
+pub fn save_file(
+    title: &str,
+    default_filename: Option<&str>,
+    filter: &[BlockingPickFilesDialogFilter],
+    starting_directory: Option<&Path>,
+) -> Result<Option<PathBuf>, BlockingDialogError> {
+    let mut args: Vec<OsString> = vec![
+        "--title".into(),
+        title.into(),
+        "--getsavefilename".into(),
+        get_start_path(starting_directory, default_filename),
+    ];
+
+    if !filter.is_empty() {
+        args.push(get_filter(filter).into());
+    }
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_paths).and_then(|mut paths| paths.pop()))
+}
+
+pub fn pick_directory(
+    title: &str,
+    starting_directory: Option<&Path>,
+) -> Result<Option<PathBuf>, BlockingDialogError> {
+    let args: [OsString; 4] = [
+        "--title".into(),
+        title.into(),
+        "--getexistingdirectory".into(),
+        get_start_path(starting_directory, None),
+    ];
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_paths).and_then(|mut paths| paths.pop()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::os_dialog::subprocess::tests::Stub;
+
+    #[test]
+    fn alerts() {
+        let stub = Stub::new(PROGRAM, 0, "");
+        alert("Title", "Text", BlockingDialogLevel::Warning).unwrap();
+        assert_eq!(stub.args(), ["--title", "Title", "--sorry", "Text"]);
+    }
+
+    #[test]
+    fn confirms() {
+        let stub = Stub::new(PROGRAM, 0, "");
+        let level = BlockingDialogLevel::Info;
+        assert!(confirm("Title", "Text", level, Some("Go"), Some("Stay")).unwrap());
+        assert_eq!(
+            stub.args(),
+            [
+                "--title",
+                "Title",
+                "--yesno",
+                "Text",
+                "--yes-label",
+                "Go",
+                "--no-label",
+                "Stay",
+            ]
+        );
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert!(!confirm("Title", "Text", level, None, None).unwrap());
+    }
+
+    #[test]
+    fn confirms_warnings() {
+        let stub = Stub::new(PROGRAM, 0, "");
+        let level = BlockingDialogLevel::Warning;
+        assert!(confirm("Title", "Text", level, Some("Go"), None).unwrap());
+        assert_eq!(
+            stub.args(),
+            [
+                "--title",
+                "Title",
+                "--warningcontinuecancel",
+                "Text",
+                "--continue-label",
+                "Go",
+            ]
+        );
+
+        for code in [1, 2] {
+            let _stub = Stub::new(PROGRAM, code, "");
+            assert!(!confirm("Title", "Text", level, None, None).unwrap());
+        }
+
+        let _stub = Stub::new(PROGRAM, 5, "");
+        assert!(confirm("Title", "Text", level, None, None).is_err());
+    }
+
+    #[test]
+    fn shows_messages() {
+        let level = BlockingDialogLevel::Info;
+        let buttons = ["Save", "Discard", "Cancel"];
+
+        for code in [0, 1, 2] {
+            let stub = Stub::new(PROGRAM, code, "");
+            let index = message("Title", "Text", level, &buttons, 0, 2).unwrap();
+            assert_eq!(index, code as usize);
+            assert_eq!(
+                stub.args(),
+                [
+                    "--title",
+                    "Title",
+                    "--yesnocancel",
+                    "Text",
+                    "--yes-label",
+                    "Save",
+                    "--no-label",
+                    "Discard",
+                    "--cancel-label",
+                    "Cancel",
+                ]
+            );
+        }
+
+        let stub = Stub::new(PROGRAM, 1, "");
+        let level = BlockingDialogLevel::Warning;
+        let index = message("Title", "Text", level, &buttons[..2], 0, 1).unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(stub.args()[2], "--warningyesno");
+
+        let _stub = Stub::new(PROGRAM, 5, "");
+        assert!(message("Title", "Text", level, &buttons, 0, 2).is_err());
+    }
+
+    #[test]
+    fn shows_messages_as_menus() {
+        let level = BlockingDialogLevel::Info;
+        let buttons = ["Cancel", "Save", "Discard"];
+        let menu = [
+            "--title",
+            "Title",
+            "--menu",
+            "Text",
+            "0",
+            "Cancel",
+            "1",
+            "Save",
+            "2",
+            "Discard",
+            "--default",
+            "Save",
+        ];
+
+        // The fixed layouts can't focus another button or cancel with the first one
+        let stub = Stub::new(PROGRAM, 0, "2");
+        assert_eq!(message("Title", "Text", level, &buttons, 1, 0).unwrap(), 2);
+        assert_eq!(stub.args(), menu);
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert_eq!(message("Title", "Text", level, &buttons, 1, 0).unwrap(), 0);
+
+        let _stub = Stub::new(PROGRAM, 0, "7");
+        assert_eq!(message("Title", "Text", level, &buttons, 1, 0).unwrap(), 0);
+
+        let stub = Stub::new(PROGRAM, 0, "1");
+        let index = message("Title", "Text", level, &buttons[1..], 1, 0).unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(
+            stub.args(),
+            [
+                "--title",
+                "Title",
+                "--menu",
+                "Text",
+                "0",
+                "Save",
+                "1",
+                "Discard",
+                "--default",
+                "Discard",
+            ]
+        );
+
+        let stub = Stub::new(PROGRAM, 0, "3");
+        let buttons = ["A", "B", "C", "D"];
+        assert_eq!(message("Title", "Text", level, &buttons, 0, 3).unwrap(), 3);
+        assert_eq!(stub.args()[2], "--menu");
+    }
+
+    #[test]
+    fn asks_questions() {
+        let level = BlockingDialogLevel::Error;
+        let responses = [
+            (0, BlockingQuestionDialogResponse::Yes),
+            (1, BlockingQuestionDialogResponse::No),
+            (2, BlockingQuestionDialogResponse::Cancel),
+        ];
+
+        for (code, expected) in responses {
+            let stub = Stub::new(PROGRAM, code, "");
+            assert_eq!(question("Title", "Text", level).unwrap(), expected);
+            assert_eq!(
+                stub.args(),
+                ["--title", "Title", "--warningyesnocancel", "Text"]
+            );
+        }
+
+        let _stub = Stub::new(PROGRAM, 5, "");
+        assert!(question("Title", "Text", level).is_err());
+    }
+
+    #[test]
+    fn asks_for_input() {
+        let stub = Stub::new(PROGRAM, 0, "typed\n");
+        let text = input("Title", "Name:", Some("default")).unwrap();
+        assert_eq!(text.as_deref(), Some("typed"));
+        assert_eq!(
+            stub.args(),
+            ["--title", "Title", "--inputbox", "Name:", "default"]
+        );
+
+        let stub = Stub::new(PROGRAM, 0, "s3cret\n");
+        let secret = password("Title", "Password:").unwrap().unwrap();
+        assert_eq!(secret.expose(), "s3cret");
+        assert_eq!(stub.args(), ["--title", "Title", "--password", "Password:"]);
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert!(input("Title", "Name:", None).unwrap().is_none());
+        assert!(login("Title", "User:", None).unwrap().is_none());
+    }
+
+    #[test]
+    fn lists_rows() {
+        let rows: &[&[&str]] = &[&["a", "1"], &["b"]];
+
+        let stub = Stub::new(PROGRAM, 0, "1\n0\n");
+        let mode = BlockingListDialogMode::Checklist;
+        assert_eq!(list("Title", "Text", rows, &[1], mode).unwrap(), [0, 1]);
+        assert_eq!(
+            stub.args(),
+            [
+                "--title",
+                "Title",
+                "--checklist",
+                "Text",
+                "0",
+                "a - 1",
+                "off",
+                "1",
+                "b",
+                "on",
+                "--separate-output",
+            ]
+        );
+
+        let stub = Stub::new(PROGRAM, 0, "1\n");
+        let mode = BlockingListDialogMode::Plain;
+        assert_eq!(list("Title", "Text", rows, &[1], mode).unwrap(), [1]);
+        assert_eq!(stub.args()[8..], ["--default", "b"]);
+    }
+
+    #[test]
+    fn picks_colors() {
+        let stub = Stub::new(PROGRAM, 0, "#ff8000\n");
+        let color = color("Title", Some(Rgba::new(1, 2, 3, 4))).unwrap();
+        assert_eq!(color, Some(Rgba::rgb(255, 128, 0)));
+        assert_eq!(
+            stub.args(),
+            ["--title", "Title", "--getcolor", "--default", "#010203"]
+        );
+
+        let _stub = Stub::new(PROGRAM, 0, "not a color");
+        assert!(self::color("Title", None).is_err());
+    }
+
+    #[test]
+    fn picks_files() {
+        let filter = [BlockingPickFilesDialogFilter {
+            name: "Images",
+            extensions: &["png", "jpg"],
+        }];
+
+        let stub = Stub::new(PROGRAM, 0, "/tmp/a b.png\n/tmp/c.jpg\n");
+        let paths = pick_files("Title", true, &filter, Some(Path::new("/tmp"))).unwrap();
+        assert_eq!(
+            paths,
+            [PathBuf::from("/tmp/a b.png"), PathBuf::from("/tmp/c.jpg")]
+        );
+        assert_eq!(
+            stub.args(),
+            [
+                "--title",
+                "Title",
+                "--multiple",
+                "--separate-output",
+                "--getopenfilename",
+                "/tmp",
+                "Images (*.png *.jpg)",
+            ]
+        );
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert!(pick_files("Title", false, &[], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn saves_files_and_picks_directories() {
+        let stub = Stub::new(PROGRAM, 0, "/tmp/out.png\n");
+        let path = save_file("Title", Some("out.png"), &[], Some(Path::new("/tmp"))).unwrap();
+        assert_eq!(path, Some(PathBuf::from("/tmp/out.png")));
+        assert_eq!(
+            stub.args(),
+            ["--title", "Title", "--getsavefilename", "/tmp/out.png"]
+        );
+
+        let stub = Stub::new(PROGRAM, 0, "/home\n");
+        assert_eq!(
+            pick_directory("Title", None).unwrap(),
+            Some(PathBuf::from("/home"))
+        );
+        assert_eq!(
+            stub.args(),
+            ["--title", "Title", "--getexistingdirectory", "."]
+        );
+    }
+}
@@ -0,0 +1,36 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{kdialog, tty, zenity};
+use crate::{BlockingDialogError, BlockingListDialog};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingListDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Vec<usize>, BlockingDialogError> {
+        let selected = self.get_selected();
+
+        match backend::select(&[Backend::Zenity, Backend::Kdialog, Backend::Tty])? {
+            Backend::Zenity => zenity::list(
+                self.title,
+                self.message,
+                self.columns,
+                self.rows,
+                &selected,
+                self.mode,
+            ),
+            Backend::Kdialog => {
+                kdialog::list(self.title, self.message, self.rows, &selected, self.mode)
+            }
+            Backend::Tty => tty::list(
+                self.title,
+                self.message,
+                self.columns,
+                self.rows,
+                selected,
+                self.mode,
+            ),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{kdialog, tty, zenity};
+use crate::{BlockingDialogError, BlockingLoginDialog, Credentials};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingLoginDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Credentials>, BlockingDialogError> {
+        match backend::select(&[Backend::Zenity, Backend::Kdialog, Backend::Tty])? {
+            Backend::Zenity => zenity::login(self.title, self.message),
+            Backend::Kdialog => kdialog::login(self.title, self.message, self.username),
+            Backend::Tty => tty::login(self.title, self.message, self.username),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
@@ -0,0 +1,354 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::terminal::{Key, Terminal, truncate};
+use crate::{BlockingDialogError, BlockingListDialogMode};
+use std::io::Write;
+
+/// Pads each cell to its column's width, so rows line up under the header
+pub fn format_rows(columns: &[&str], rows: &[&[&str]]) -> (String, Vec<String>) {
+    let mut widths = columns
+        .iter()
+        .map(|column| column.chars().count())
+        .collect::<Vec<_>>();
+
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            match widths.get_mut(i) {
+                Some(width) => *width = (*width).max(cell.chars().count()),
+                None => widths.push(cell.chars().count()),
+            }
+        }
+    }
+
+    let format = |cells: &[&str]| {
+        widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| format!("{:width$}", cells.get(i).copied().unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    (
+        format(columns),
+        rows.iter().map(|row| format(row)).collect(),
+    )
+}
+
+struct Menu<'a> {
+    title: &'a str,
+    message: &'a str,
+    header: String,
+    rows: Vec<String>,
+    mode: BlockingListDialogMode,
+    /// Marked rows, the chosen one for radio lists
+    selected: Vec<usize>,
+    cursor: usize,
+    scroll: usize,
+}
+
+enum Action {
+    Continue,
+    Cancel,
+    Done(Vec<usize>),
+}
+
+impl Menu<'_> {
+    /// Rows taken by everything but the list
+    fn chrome(&self) -> usize {
+        let message = if self.message.is_empty() { 0 } else { 1 };
+        let header = if self.header.is_empty() { 0 } else { 1 };
+        2 + message + header
+    }
+
+    fn render(&mut self, terminal: &mut Terminal) -> Result<(), BlockingDialogError> {
+        let (rows, cols) = terminal.size();
+        let list_rows = rows.saturating_sub(self.chrome()).max(1);
+
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + list_rows {
+            self.scroll = self.cursor + 1 - list_rows;
+        }
+
+        let indent = match self.mode {
+            BlockingListDialogMode::Plain => "",
+            _ => "    ",
+        };
+
+        let mut out = String::from("\x1b[H");
+
+        out.push_str(&format!(
+            "\x1b[1m{}\x1b[0m\x1b[K\r\n",
+            truncate(self.title, cols)
+        ));
+
+        if !self.message.is_empty() {
+            out.push_str(&format!("{}\x1b[K\r\n", truncate(self.message, cols)));
+        }
+
+        if !self.header.is_empty() {
+            let header = format!("{indent}{}", self.header);
+            out.push_str(&format!(
+                "\x1b[4m{}\x1b[0m\x1b[K\r\n",
+                truncate(&header, cols)
+            ));
+        }
+
+        for row in 0..list_rows {
+            let index = self.scroll + row;
+            let Some(text) = self.rows.get(index) else {
+                out.push_str("\x1b[K\r\n");
+                continue;
+            };
+
+            let marked = self.selected.contains(&index);
+            let mark = match (self.mode, marked) {
+                (BlockingListDialogMode::Radio, true) => "(*) ",
+                (BlockingListDialogMode::Radio, false) => "( ) ",
+                (BlockingListDialogMode::Checklist, true) => "[x] ",
+                (BlockingListDialogMode::Checklist, false) => "[ ] ",
+                (BlockingListDialogMode::Plain, _) => "",
+            };
+
+            let line = format!("{mark}{text}");
+            let line = truncate(&line, cols);
+
+            if index == self.cursor {
+                out.push_str(&format!("\x1b[7m{line}\x1b[0m\x1b[K\r\n"));
+            } else {
+                out.push_str(&format!("{line}\x1b[K\r\n"));
+            }
+        }
+
+        let help = match self.mode {
+            BlockingListDialogMode::Plain => "Enter: select  Esc: cancel",
+            _ => "Space: mark  Enter: done  Esc: cancel",
+        };
+        out.push_str(&format!("\x1b[2m{}\x1b[0m\x1b[J", truncate(help, cols)));
+
+        terminal.write_all(out.as_bytes())?;
+        terminal.flush()?;
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: Key, page: usize) -> Action {
+        let last = self.rows.len().saturating_sub(1);
+
+        match key {
+            Key::Up => self.cursor = self.cursor.saturating_sub(1),
+            Key::Down => self.cursor = (self.cursor + 1).min(last),
+            Key::PageUp => self.cursor = self.cursor.saturating_sub(page),
+            Key::PageDown => self.cursor = (self.cursor + page).min(last),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = last,
+            Key::Char(' ') if !self.rows.is_empty() => match self.mode {
+                BlockingListDialogMode::Radio => self.selected = vec![self.cursor],
+                BlockingListDialogMode::Checklist => {
+                    match self.selected.iter().position(|i| *i == self.cursor) {
+                        Some(i) => {
+                            self.selected.remove(i);
+                        }
+                        None => self.selected.push(self.cursor),
+                    }
+                }
+                BlockingListDialogMode::Plain => return Action::Done(vec![self.cursor]),
+            },
+            Key::Enter if self.rows.is_empty() => return Action::Cancel,
+            // Nothing marked in a radio list picks the highlighted row
+            Key::Enter => {
+                return match self.mode {
+                    BlockingListDialogMode::Checklist => {
+                        let mut selected = self.selected.clone();
+                        selected.sort_unstable();
+                        Action::Done(selected)
+                    }
+                    _ => Action::Done(vec![*self.selected.first().unwrap_or(&self.cursor)]),
+                };
+            }
+            Key::Escape | Key::Interrupt => return Action::Cancel,
+            _ => {}
+        }
+
+        Action::Continue
+    }
+}
+
+pub fn run(
+    title: &str,
+    message: &str,
+    columns: &[&str],
+    rows: &[&[&str]],
+    selected: Vec<usize>,
+    mode: BlockingListDialogMode,
+) -> Result<Vec<usize>, BlockingDialogError> {
+    let (header, rows) = format_rows(columns, rows);
+
+    let mut menu = Menu {
+        title,
+        message,
+        header,
+        rows,
+        mode,
+        cursor: selected.first().copied().unwrap_or_default(),
+        selected: match mode {
+            BlockingListDialogMode::Plain => Vec::new(),
+            _ => selected,
+        },
+        scroll: 0,
+    };
+
+    let mut terminal = Terminal::open()?;
+
+    loop {
+        menu.render(&mut terminal)?;
+
+        let page = terminal.size().0.saturating_sub(menu.chrome()).max(1);
+        let key = terminal.read_key()?;
+
+        match menu.handle_key(key, page) {
+            Action::Continue => {}
+            Action::Cancel => return Ok(Vec::new()),
+            Action::Done(selected) => return Ok(selected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::os_dialog::tty::{self, tests::Pty, tests::run_in_child};
+
+    const COLUMNS: &[&str] = &["Name", "Size"];
+    const ROWS: &[&[&str]] = &[
+        &["a.txt", "1 KB"],
+        &["b.txt", "20 KB"],
+        &["c.txt", "300 KB"],
+    ];
+
+    fn menu(mode: BlockingListDialogMode, selected: Vec<usize>) -> Menu<'static> {
+        let (header, rows) = format_rows(COLUMNS, ROWS);
+
+        Menu {
+            title: "Files",
+            message: "",
+            header,
+            rows,
+            mode,
+            selected,
+            cursor: 0,
+            scroll: 0,
+        }
+    }
+
+    fn done(action: Action) -> Vec<usize> {
+        match action {
+            Action::Done(selected) => selected,
+            Action::Continue => panic!("expected Done, got Continue"),
+            Action::Cancel => panic!("expected Done, got Cancel"),
+        }
+    }
+
+    #[test]
+    fn formats_rows() {
+        let (header, rows) = format_rows(COLUMNS, ROWS);
+        assert_eq!(header, "Name   Size");
+        assert_eq!(rows, ["a.txt  1 KB", "b.txt  20 KB", "c.txt  300 KB"]);
+
+        let (header, rows) = format_rows(&[], &[&["a", "b"]]);
+        assert_eq!(header, "");
+        assert_eq!(rows, ["a  b"]);
+    }
+
+    #[test]
+    fn navigates() {
+        let mut menu = menu(BlockingListDialogMode::Plain, Vec::new());
+
+        let mut press = |key| {
+            assert!(matches!(menu.handle_key(key, 2), Action::Continue));
+            menu.cursor
+        };
+
+        assert_eq!(press(Key::Down), 1);
+        assert_eq!(press(Key::Down), 2);
+        assert_eq!(press(Key::Down), 2);
+        assert_eq!(press(Key::Up), 1);
+        assert_eq!(press(Key::Home), 0);
+        assert_eq!(press(Key::PageDown), 2);
+        assert_eq!(press(Key::PageUp), 0);
+        assert_eq!(press(Key::End), 2);
+
+        assert_eq!(done(menu.handle_key(Key::Enter, 2)), [2]);
+        assert!(matches!(menu.handle_key(Key::Escape, 2), Action::Cancel));
+        assert!(matches!(menu.handle_key(Key::Interrupt, 2), Action::Cancel));
+    }
+
+    #[test]
+    fn toggles_checklist_marks() {
+        let mut menu = menu(BlockingListDialogMode::Checklist, vec![1]);
+
+        menu.cursor = 2;
+        menu.handle_key(Key::Char(' '), 2);
+        assert_eq!(menu.selected, [1, 2]);
+
+        menu.cursor = 1;
+        menu.handle_key(Key::Char(' '), 2);
+        assert_eq!(menu.selected, [2]);
+
+        menu.cursor = 0;
+        menu.handle_key(Key::Char(' '), 2);
+        assert_eq!(done(menu.handle_key(Key::Enter, 2)), [0, 2]);
+    }
+
+    #[test]
+    fn chooses_radio_rows() {
+        let mut menu = menu(BlockingListDialogMode::Radio, Vec::new());
+
+        // Nothing marked picks the highlighted row
+        menu.cursor = 1;
+        assert_eq!(done(menu.handle_key(Key::Enter, 2)), [1]);
+
+        menu.cursor = 2;
+        menu.handle_key(Key::Char(' '), 2);
+        menu.cursor = 0;
+        menu.handle_key(Key::Char(' '), 2);
+        assert_eq!(menu.selected, [0]);
+
+        menu.cursor = 1;
+        assert_eq!(done(menu.handle_key(Key::Enter, 2)), [0]);
+    }
+
+    #[test]
+    fn cancels_empty_lists() {
+        let mut menu = menu(BlockingListDialogMode::Checklist, Vec::new());
+        menu.rows.clear();
+
+        assert!(matches!(
+            menu.handle_key(Key::Char(' '), 2),
+            Action::Continue
+        ));
+        assert!(menu.selected.is_empty());
+        assert!(matches!(menu.handle_key(Key::Enter, 2), Action::Cancel));
+    }
+
+    #[test]
+    fn checks_rows_in_a_terminal() {
+        let dialog = || {
+            let mode = BlockingListDialogMode::Checklist;
+            tty::list("Files", "Pick some", COLUMNS, ROWS, vec![1], mode)
+        };
+        if run_in_child(dialog) {
+            return;
+        }
+
+        let path = concat!(module_path!(), "::checks_rows_in_a_terminal");
+        let mut pty = Pty::spawn(path, "xterm", &[]);
+        pty.expect("[x] b.txt");
+        pty.send(" \x1b[B \x1b[H \r");
+        assert_eq!(pty.result(), "Ok([0, 2])");
+    }
+}
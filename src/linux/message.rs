@@ -0,0 +1,45 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{kdialog, tty, zenity};
+use crate::{BlockingDialogError, BlockingMessageDialog};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingMessageDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<usize, BlockingDialogError> {
+        let buttons = self.get_buttons();
+        let default_button = self.get_default_button();
+        let cancel_button = self.get_cancel_button();
+
+        // zenity always focuses the first button
+        let backends: &[Backend] = if default_button == 0 {
+            &[Backend::Zenity, Backend::Kdialog, Backend::Tty]
+        } else {
+            &[Backend::Kdialog, Backend::Tty]
+        };
+
+        match backend::select(backends)? {
+            Backend::Zenity => {
+                zenity::message(self.title, self.message, self.level, buttons, cancel_button)
+            }
+            Backend::Kdialog => kdialog::message(
+                self.title,
+                self.message,
+                self.level,
+                buttons,
+                default_button,
+                cancel_button,
+            ),
+            Backend::Tty => tty::message(
+                self.title,
+                self.message,
+                self.level,
+                buttons,
+                default_button,
+                cancel_button,
+            ),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
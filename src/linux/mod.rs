@@ -1,6 +1,31 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+pub mod alert;
+pub mod backend;
+pub mod browser;
+pub mod color;
+pub mod confirm;
+pub mod date;
+pub mod dbus;
+pub mod form;
+pub mod input;
+pub mod kdialog;
+pub mod list;
+pub mod login;
+pub mod menu;
+pub mod message;
+pub mod password;
 pub mod pick_directory;
 pub mod pick_files;
+pub mod portal;
+pub mod progress;
+pub mod progress_bar;
+pub mod question;
+pub mod rgba;
 pub mod save_file;
+pub mod subprocess;
+pub mod terminal;
+pub mod tty;
+pub mod window_identifier;
+pub mod zenity;
@@ -0,0 +1,18 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{kdialog, tty, zenity};
+use crate::{BlockingDialogError, BlockingPasswordDialog, Secret};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPasswordDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Secret>, BlockingDialogError> {
+        match backend::select(&[Backend::Zenity, Backend::Kdialog, Backend::Tty])? {
+            Backend::Zenity => zenity::password(self.title),
+            Backend::Kdialog => kdialog::password(self.title, self.message),
+            Backend::Tty => tty::password(self.title, self.message),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
@@ -1,18 +1,38 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use super::backend::{self, Backend};
+use super::dbus::Connection;
+use super::window_identifier::WindowIdentifier;
+use super::{kdialog, portal, tty, zenity};
 use crate::{BlockingDialogError, BlockingPickDirectoryDialog};
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
-use rfd::FileDialog;
 use std::path::PathBuf;
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickDirectoryDialog<'a, W> {
-    pub fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
-        let result = FileDialog::new()
-            .set_title(self.title)
-            .set_parent(&self.window)
-            .pick_folder();
-
-        Ok(result)
+    pub(crate) fn show_native(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+        match backend::select(&[
+            Backend::Portal,
+            Backend::Zenity,
+            Backend::Kdialog,
+            Backend::Tty,
+        ])? {
+            Backend::Portal => {
+                let parent = WindowIdentifier::new(&self.window)?;
+                let mut paths = portal::open_file(
+                    &mut Connection::session()?,
+                    parent.as_str(),
+                    self.title,
+                    false,
+                    true,
+                    &[],
+                    self.starting_directory,
+                )?;
+                Ok(paths.pop())
+            }
+            Backend::Zenity => zenity::pick_directory(self.title, self.starting_directory),
+            Backend::Kdialog => kdialog::pick_directory(self.title, self.starting_directory),
+            Backend::Tty => tty::pick_directory(self.title, self.starting_directory),
+        }
     }
 }
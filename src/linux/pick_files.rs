@@ -1,31 +1,52 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use super::backend::{self, Backend};
+use super::dbus::Connection;
+use super::window_identifier::WindowIdentifier;
+use super::{kdialog, portal, tty, zenity};
 use crate::{BlockingDialogError, BlockingPickFilesDialog};
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
-use rfd::FileDialog;
 use std::path::PathBuf;
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickFilesDialog<'a, W> {
-    pub fn show(&self) -> Result<Vec<PathBuf>, BlockingDialogError> {
-        let mut dialog = FileDialog::new()
-            .set_title(self.title)
-            .set_parent(&self.window);
-
-        for entry in self.filter {
-            dialog = dialog.add_filter(entry.name, entry.extensions);
-        }
-
-        if self.multiple {
-            match dialog.pick_files() {
-                Some(files) => Ok(files),
-                None => Ok(Vec::new()),
-            }
-        } else {
-            match dialog.pick_file() {
-                Some(file) => Ok(vec![file]),
-                None => Ok(Vec::new()),
+    pub(crate) fn show_native(&self) -> Result<Vec<PathBuf>, BlockingDialogError> {
+        match backend::select(&[
+            Backend::Portal,
+            Backend::Zenity,
+            Backend::Kdialog,
+            Backend::Tty,
+        ])? {
+            Backend::Portal => {
+                let parent = WindowIdentifier::new(&self.window)?;
+                portal::open_file(
+                    &mut Connection::session()?,
+                    parent.as_str(),
+                    self.title,
+                    self.multiple,
+                    false,
+                    self.filter,
+                    self.starting_directory,
+                )
             }
+            Backend::Zenity => zenity::pick_files(
+                self.title,
+                self.multiple,
+                self.filter,
+                self.starting_directory,
+            ),
+            Backend::Kdialog => kdialog::pick_files(
+                self.title,
+                self.multiple,
+                self.filter,
+                self.starting_directory,
+            ),
+            Backend::Tty => tty::pick_files(
+                self.title,
+                self.multiple,
+                self.filter,
+                self.starting_directory,
+            ),
         }
     }
 }
@@ -0,0 +1,403 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::dbus::{Connection, Value};
+use crate::{BlockingDialogError, BlockingPickFilesDialogFilter, Rgba};
+use std::ffi::OsString;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PATH: &str = "/org/freedesktop/portal/desktop";
+const FILE_CHOOSER: &str = "org.freedesktop.portal.FileChooser";
+const SCREENSHOT: &str = "org.freedesktop.portal.Screenshot";
+const REQUEST: &str = "org.freedesktop.portal.Request";
+
+static TOKEN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+fn get_filters(filter: &[BlockingPickFilesDialogFilter]) -> Value {
+    let filters = filter
+        .iter()
+        .map(|entry| {
+            let patterns = entry
+                .extensions
+                .iter()
+                .map(|ext| Value::Struct(vec![Value::U32(0), Value::Str(format!("*.{ext}"))]))
+                .collect();
+
+            Value::Struct(vec![
+                Value::Str(entry.name.to_string()),
+                Value::Array("(us)".to_string(), patterns),
+            ])
+        })
+        .collect();
+
+    Value::Array("(sa(us))".to_string(), filters)
+}
+
+fn decode_uri(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = path.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    Some(PathBuf::from(OsString::from_vec(decoded)))
+}
+
+/// Calls a portal method that answers with a Response signal, and returns
+/// the results, or `None` if the user cancelled
+fn call(
+    conn: &mut Connection,
+    interface: &str,
+    method: &str,
+    mut args: Vec<Value>,
+    mut options: Vec<(&str, Value)>,
+) -> Result<Option<Value>, BlockingDialogError> {
+    // Subscribe to the Response signal before making the call, so that we
+    // can't miss it if the portal answers immediately
+    let token = format!(
+        "blocking_dialog_{}_{}",
+        std::process::id(),
+        TOKEN_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let sender = conn.unique_name().trim_start_matches(':').replace('.', "_");
+    let mut request_path = format!("{PATH}/request/{sender}/{token}");
+    conn.add_match(&match_rule(&request_path))?;
+
+    options.push(("handle_token", Value::Str(token)));
+    args.push(Value::dict(options));
+
+    let reply = conn.call_method(DESTINATION, PATH, interface, method, args)?;
+
+    // Older portals ignore handle_token and pick their own path
+    if let Some(handle) = reply.body.first().and_then(Value::as_str)
+        && handle != request_path
+    {
+        request_path = handle.to_string();
+        conn.add_match(&match_rule(&request_path))?;
+    }
+
+    let mut msg = conn.wait_for_signal(&request_path, REQUEST, "Response")?;
+
+    match msg.body.first().and_then(Value::as_u32) {
+        Some(0) => {}
+        Some(1) => return Ok(None),
+        _ => {
+            return Err(BlockingDialogError::DBus(
+                "The portal request was ended unexpectedly".to_string(),
+            ));
+        }
+    }
+
+    if msg.body.len() < 2 {
+        return Ok(Some(Value::dict([])));
+    }
+
+    Ok(Some(msg.body.swap_remove(1)))
+}
+
+fn request(
+    conn: &mut Connection,
+    method: &str,
+    parent_window: &str,
+    title: &str,
+    mut options: Vec<(&str, Value)>,
+) -> Result<Option<Vec<PathBuf>>, BlockingDialogError> {
+    options.push(("modal", Value::Bool(true)));
+
+    let args = vec![
+        Value::Str(parent_window.to_string()),
+        Value::Str(title.to_string()),
+    ];
+
+    let Some(results) = call(conn, FILE_CHOOSER, method, args, options)? else {
+        return Ok(None);
+    };
+
+    let paths = results
+        .get("uris")
+        .and_then(Value::as_array)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(Value::as_str)
+        .filter_map(decode_uri)
+        .collect();
+
+    Ok(Some(paths))
+}
+
+fn match_rule(request_path: &str) -> String {
+    format!(
+        "type='signal',sender='{DESTINATION}',path='{request_path}',interface='{REQUEST}',member='Response'"
+    )
+}
+
+pub fn open_file(
+    conn: &mut Connection,
+    parent_window: &str,
+    title: &str,
+    multiple: bool,
+    directory: bool,
+    filter: &[BlockingPickFilesDialogFilter],
+    current_folder: Option<&Path>,
+) -> Result<Vec<PathBuf>, BlockingDialogError> {
+    let mut options = vec![
+        ("multiple", Value::Bool(multiple)),
+        ("directory", Value::Bool(directory)),
+    ];
+
+    if let Some(current_folder) = current_folder {
+        let current_folder = Value::bytes(current_folder.as_os_str().as_bytes());
+        options.push(("current_folder", current_folder));
+    }
+
+    if !filter.is_empty() {
+        options.push(("filters", get_filters(filter)));
+    }
+
+    let paths = request(conn, "OpenFile", parent_window, title, options)?;
+    Ok(paths.unwrap_or_default())
+}
+
+pub fn save_file(
+    conn: &mut Connection,
+    parent_window: &str,
+    title: &str,
+    current_name: Option<&str>,
+    filter: &[BlockingPickFilesDialogFilter],
+    current_folder: Option<&Path>,
+) -> Result<Option<PathBuf>, BlockingDialogError> {
+    let mut options = Vec::new();
+
+    if let Some(current_name) = current_name {
+        options.push(("current_name", Value::Str(current_name.to_string())));
+    }
+
+    if let Some(current_folder) = current_folder {
+        let current_folder = Value::bytes(current_folder.as_os_str().as_bytes());
+        options.push(("current_folder", current_folder));
+    }
+
+    if !filter.is_empty() {
+        options.push(("filters", get_filters(filter)));
+    }
+
+    let paths = request(conn, "SaveFile", parent_window, title, options)?;
+    Ok(paths.and_then(|mut paths| paths.pop()))
+}
+
+/// Lets the user pick a color from the screen, so there's no initial color
+pub fn pick_color(
+    conn: &mut Connection,
+    parent_window: &str,
+) -> Result<Option<Rgba>, BlockingDialogError> {
+    let args = vec![Value::Str(parent_window.to_string())];
+
+    let Some(results) = call(conn, SCREENSHOT, "PickColor", args, Vec::new())? else {
+        return Ok(None);
+    };
+
+    // Red, green and blue from 0 to 1
+    let channels = results
+        .get("color")
+        .and_then(Value::as_struct)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(Value::as_f64)
+        .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+        .collect::<Vec<_>>();
+
+    match channels[..] {
+        [r, g, b] => Ok(Some(Rgba::rgb(r, g, b))),
+        _ => Err(BlockingDialogError::DBus(
+            "The portal returned no color".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::os_dialog::dbus::Message;
+    use crate::os_dialog::dbus::tests::TestBus;
+    use std::thread::{self, JoinHandle};
+
+    const FILTER: &[BlockingPickFilesDialogFilter] = &[BlockingPickFilesDialogFilter {
+        name: "Images",
+        extensions: &["png", "jpg"],
+    }];
+
+    /// Plays the portal's FileChooser for a single call, and returns the call
+    fn serve(bus: &TestBus, response: u32, uris: &[&str]) -> JoinHandle<Message> {
+        let mut conn = bus.connect();
+        let _ = conn
+            .call_method(
+                "org.freedesktop.DBus",
+                "/org/freedesktop/DBus",
+                "org.freedesktop.DBus",
+                "RequestName",
+                vec![Value::Str(DESTINATION.to_string()), Value::U32(0)],
+            )
+            .expect("own the portal name");
+
+        let uris = uris.iter().map(|uri| Value::Str(uri.to_string())).collect();
+        let results = Value::dict([("uris", Value::Array("s".to_string(), uris))]);
+
+        thread::spawn(move || {
+            let call = loop {
+                let msg = conn.receive().expect("receive the call");
+                if msg.interface.as_deref() == Some(FILE_CHOOSER) {
+                    break msg;
+                }
+            };
+
+            let sender = call.sender.clone().unwrap_or_default();
+            let token = call
+                .body
+                .get(2)
+                .and_then(|options| options.get("handle_token"))
+                .and_then(Value::as_str)
+                .expect("handle_token option");
+            let handle = format!(
+                "{PATH}/request/{}/{token}",
+                sender.trim_start_matches(':').replace('.', "_")
+            );
+
+            // Sent straight to the caller, so that only the path tells it apart
+            let mut other = Message::signal(&format!("{PATH}/request/other"), REQUEST, "Response")
+                .with_body(vec![Value::U32(2), Value::dict([])]);
+            other.destination = Some(sender);
+            conn.send(other).expect("send another response");
+
+            let reply =
+                Message::method_return(&call).with_body(vec![Value::ObjectPath(handle.clone())]);
+            conn.send(reply).expect("reply");
+
+            let signal = Message::signal(&handle, REQUEST, "Response")
+                .with_body(vec![Value::U32(response), results]);
+            conn.send(signal).expect("send the response");
+
+            call
+        })
+    }
+
+    fn get_option<'a>(call: &'a Message, key: &str) -> Option<&'a Value> {
+        call.body.get(2).and_then(|options| options.get(key))
+    }
+
+    #[test]
+    #[ignore = "needs dbus-daemon, run with --ignored"]
+    fn opens_files() {
+        let bus = TestBus::start();
+
+        let portal = serve(
+            &bus,
+            0,
+            &["file:///tmp/a%20b.png", "file:///tmp/caf%C3%A9.jpg"],
+        );
+        let paths = open_file(
+            &mut bus.connect(),
+            "x11:1a",
+            "Pick images",
+            true,
+            false,
+            FILTER,
+            Some(Path::new("/tmp/start dir")),
+        )
+        .expect("open_file");
+        let call = portal.join().expect("portal thread");
+
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("/tmp/a b.png"),
+                PathBuf::from("/tmp/café.jpg")
+            ]
+        );
+
+        assert_eq!(call.member.as_deref(), Some("OpenFile"));
+        assert_eq!(call.body[0].as_str(), Some("x11:1a"));
+        assert_eq!(call.body[1].as_str(), Some("Pick images"));
+        assert_eq!(get_option(&call, "multiple"), Some(&Value::Bool(true)));
+        assert_eq!(get_option(&call, "directory"), Some(&Value::Bool(false)));
+        assert_eq!(get_option(&call, "modal"), Some(&Value::Bool(true)));
+        assert_eq!(get_option(&call, "filters"), Some(&get_filters(FILTER)));
+
+        // A nul-terminated byte array, not a string
+        assert_eq!(
+            get_option(&call, "current_folder"),
+            Some(&Value::bytes(b"/tmp/start dir"))
+        );
+    }
+
+    #[test]
+    #[ignore = "needs dbus-daemon, run with --ignored"]
+    fn saves_a_file() {
+        let bus = TestBus::start();
+
+        let portal = serve(&bus, 0, &["file:///home/me/report%231.txt"]);
+        let path = save_file(
+            &mut bus.connect(),
+            "",
+            "Save report",
+            Some("report.txt"),
+            &[],
+            Some(Path::new("/home/me")),
+        )
+        .expect("save_file");
+        let call = portal.join().expect("portal thread");
+
+        assert_eq!(path, Some(PathBuf::from("/home/me/report#1.txt")));
+
+        assert_eq!(call.member.as_deref(), Some("SaveFile"));
+        assert_eq!(
+            get_option(&call, "current_name").and_then(Value::as_str),
+            Some("report.txt")
+        );
+        assert_eq!(
+            get_option(&call, "current_folder"),
+            Some(&Value::bytes(b"/home/me"))
+        );
+        assert_eq!(get_option(&call, "filters"), None);
+    }
+
+    #[test]
+    #[ignore = "needs dbus-daemon, run with --ignored"]
+    fn opens_nothing_when_cancelled() {
+        let bus = TestBus::start();
+
+        let portal = serve(&bus, 1, &["file:///tmp/ignored"]);
+        let paths =
+            open_file(&mut bus.connect(), "", "", false, true, &[], None).expect("open_file");
+        let call = portal.join().expect("portal thread");
+
+        assert!(paths.is_empty());
+        assert_eq!(get_option(&call, "current_folder"), None);
+    }
+
+    #[test]
+    #[ignore = "needs dbus-daemon, run with --ignored"]
+    fn saves_nothing_when_cancelled() {
+        let bus = TestBus::start();
+
+        let portal = serve(&bus, 1, &["file:///tmp/ignored"]);
+        let path = save_file(&mut bus.connect(), "", "", None, &[], None).expect("save_file");
+        portal.join().expect("portal thread");
+
+        assert_eq!(path, None);
+    }
+}
@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{tty, zenity};
+use crate::{BlockingDialogError, BlockingProgressDialog, Progress};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingProgressDialog<'a, W> {
+    pub(crate) fn prepare_native(&self) -> Result<Backend, BlockingDialogError> {
+        // kdialog's progress bar is driven over D-Bus, which needs its own client
+        match backend::select(&[Backend::Zenity, Backend::Tty])? {
+            backend @ (Backend::Zenity | Backend::Tty) => Ok(backend),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+
+    pub(crate) fn show_native(
+        &self,
+        backend: Backend,
+        progress: &Progress,
+    ) -> Result<(), BlockingDialogError> {
+        match backend {
+            Backend::Zenity => zenity::progress(self.title, self.message, progress),
+            Backend::Tty => tty::progress(self.title, self.message, progress),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
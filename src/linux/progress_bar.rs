@@ -0,0 +1,108 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::terminal::{Key, Terminal, truncate};
+use crate::progress::{POLL_INTERVAL, ProgressState};
+use crate::{BlockingDialogError, Progress};
+use std::io::Write;
+
+const MAX_BAR_WIDTH: usize = 60;
+/// Width of the block moving back and forth while pulsing
+const PULSE_WIDTH: usize = 6;
+
+/// A text progress bar like `[#####     ]  50%`, `tick` moves it while pulsing
+pub fn get_bar(fraction: Option<f64>, tick: usize, width: usize) -> String {
+    let inner = width.saturating_sub(7).clamp(PULSE_WIDTH, MAX_BAR_WIDTH);
+
+    match fraction {
+        Some(fraction) => {
+            let filled = (fraction * inner as f64).round() as usize;
+            let percent = (fraction * 100.0).round() as usize;
+            format!(
+                "[{}{}] {percent:>3}%",
+                "#".repeat(filled),
+                " ".repeat(inner - filled)
+            )
+        }
+        None => {
+            // Bounces between the ends
+            let span = inner - PULSE_WIDTH;
+            let offset = match span {
+                0 => 0,
+                span => {
+                    let step = tick % (2 * span);
+                    step.min(2 * span - step)
+                }
+            };
+
+            format!(
+                "[{}{}{}]",
+                " ".repeat(offset),
+                "#".repeat(PULSE_WIDTH),
+                " ".repeat(span - offset)
+            )
+        }
+    }
+}
+
+fn render(
+    terminal: &mut Terminal,
+    title: &str,
+    message: &str,
+    state: &ProgressState,
+    tick: usize,
+    cancelled: bool,
+) -> Result<(), BlockingDialogError> {
+    let (_, cols) = terminal.size();
+    let text = state.text.as_deref().unwrap_or(message);
+
+    let mut out = String::from("\x1b[H");
+
+    out.push_str(&format!(
+        "\x1b[1m{}\x1b[0m\x1b[K\r\n",
+        truncate(title, cols)
+    ));
+    out.push_str(&format!("{}\x1b[K\r\n", truncate(text, cols)));
+    out.push_str(&format!(
+        "{}\x1b[K\r\n",
+        get_bar(state.fraction, tick, cols)
+    ));
+
+    let help = if cancelled {
+        "Cancelling..."
+    } else {
+        "Esc: cancel"
+    };
+    out.push_str(&format!("\x1b[2m{}\x1b[0m\x1b[J", truncate(help, cols)));
+
+    terminal.write_all(out.as_bytes())?;
+    terminal.flush()?;
+
+    Ok(())
+}
+
+/// Shows the bar until the worker finishes, Escape only asks it to stop
+pub fn run(title: &str, message: &str, progress: &Progress) -> Result<(), BlockingDialogError> {
+    let mut terminal = Terminal::open()?;
+    let mut tick = 0;
+
+    while !progress.is_finished() {
+        let state = progress.get_state();
+        render(
+            &mut terminal,
+            title,
+            message,
+            &state,
+            tick,
+            progress.is_cancelled(),
+        )?;
+
+        if let Some(Key::Escape | Key::Interrupt) = terminal.poll_key(POLL_INTERVAL)? {
+            progress.cancel();
+        }
+
+        tick += 1;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::backend::{self, Backend};
+use super::{kdialog, tty, zenity};
+use crate::{BlockingDialogError, BlockingQuestionDialog, BlockingQuestionDialogResponse};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingQuestionDialog<'a, W> {
+    pub(crate) fn show_native(
+        &self,
+    ) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+        match backend::select(&[Backend::Zenity, Backend::Kdialog, Backend::Tty])? {
+            Backend::Zenity => zenity::question(self.title, self.message, self.level),
+            Backend::Kdialog => kdialog::question(self.title, self.message, self.level),
+            Backend::Tty => tty::question(self.title, self.message, self.level),
+            backend => Err(backend::unsupported(backend)),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::Rgba;
+
+/// Formats a color as `#rrggbb`, or `#rrggbbaa` if `alpha` is set
+pub fn to_hex(color: Rgba, alpha: bool) -> String {
+    let Rgba { r, g, b, a } = color;
+
+    if alpha {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// Formats a color the way GTK parses it, with the alpha from 0 to 1
+pub fn to_css(color: Rgba) -> String {
+    let Rgba { r, g, b, a } = color;
+    format!("rgba({r},{g},{b},{})", a as f64 / 255.0)
+}
+
+fn parse_hex(hex: &str) -> Option<Rgba> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |i: usize, len: usize| {
+        let value = u8::from_str_radix(&hex[i * len..(i + 1) * len], 16).ok()?;
+        // #rgb is short for #rrggbb
+        Some(if len == 1 { value * 17 } else { value })
+    };
+
+    match hex.len() {
+        3 => Some(Rgba::rgb(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?)),
+        6 => Some(Rgba::rgb(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?)),
+        8 => Some(Rgba::new(
+            channel(0, 2)?,
+            channel(1, 2)?,
+            channel(2, 2)?,
+            channel(3, 2)?,
+        )),
+        _ => None,
+    }
+}
+
+/// Parses `rgb(r,g,b)` or `rgba(r,g,b,a)`, with channels from 0 to 255
+/// and the alpha from 0 to 1
+fn parse_function(s: &str) -> Option<Rgba> {
+    let (name, args) = s.strip_suffix(')')?.split_once('(')?;
+    let args = args.split(',').map(str::trim).collect::<Vec<_>>();
+
+    let channel = |arg: &str| {
+        let value = arg.parse::<f64>().ok()?;
+        (0.0..=255.0)
+            .contains(&value)
+            .then_some(value.round() as u8)
+    };
+
+    match (name.trim(), &args[..]) {
+        ("rgb", [r, g, b]) => Some(Rgba::rgb(channel(r)?, channel(g)?, channel(b)?)),
+        ("rgba", [r, g, b, a]) => {
+            let a = a.parse::<f64>().ok().filter(|a| (0.0..=1.0).contains(a))?;
+            let a = (a * 255.0).round() as u8;
+            Some(Rgba::new(channel(r)?, channel(g)?, channel(b)?, a))
+        }
+        _ => None,
+    }
+}
+
+/// Parses a color printed by a dialog, or typed in the terminal
+pub fn parse(s: &str) -> Option<Rgba> {
+    let s = s.trim().to_ascii_lowercase();
+
+    match s.strip_prefix('#') {
+        Some(hex) => parse_hex(hex),
+        None => parse_function(&s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex() {
+        assert_eq!(parse("#1a2b3c"), Some(Rgba::rgb(0x1a, 0x2b, 0x3c)));
+        assert_eq!(parse("#1A2B3C\n"), Some(Rgba::rgb(0x1a, 0x2b, 0x3c)));
+        assert_eq!(parse("#fa0"), Some(Rgba::rgb(0xff, 0xaa, 0x00)));
+        assert_eq!(parse("#1a2b3c80"), Some(Rgba::new(0x1a, 0x2b, 0x3c, 0x80)));
+    }
+
+    #[test]
+    fn parses_rgb() {
+        assert_eq!(parse("rgb(255,128,0)"), Some(Rgba::rgb(255, 128, 0)));
+        assert_eq!(parse("rgb(255, 128, 0)"), Some(Rgba::rgb(255, 128, 0)));
+        assert_eq!(
+            parse("rgba(255,128,0,0.5)"),
+            Some(Rgba::new(255, 128, 0, 128))
+        );
+        assert_eq!(parse("rgba(0,0,0,1)"), Some(Rgba::rgb(0, 0, 0)));
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("#12345"), None);
+        assert_eq!(parse("#ggg"), None);
+        assert_eq!(parse("rgb(256,0,0)"), None);
+        assert_eq!(parse("rgb(1,2)"), None);
+        assert_eq!(parse("rgba(0,0,0,2)"), None);
+        assert_eq!(parse("hsl(0,0,0)"), None);
+    }
+
+    #[test]
+    fn round_trips() {
+        let color = Rgba::new(1, 2, 3, 4);
+        assert_eq!(parse(&to_hex(color, true)), Some(color));
+        assert_eq!(parse(&to_hex(color, false)), Some(Rgba::rgb(1, 2, 3)));
+        assert_eq!(parse(&to_css(color)), Some(color));
+    }
+}
@@ -1,26 +1,51 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use super::backend::{self, Backend};
+use super::dbus::Connection;
+use super::window_identifier::WindowIdentifier;
+use super::{kdialog, portal, tty, zenity};
 use crate::{BlockingDialogError, BlockingSaveFileDialog};
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
-use rfd::FileDialog;
 use std::path::PathBuf;
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingSaveFileDialog<'a, W> {
-    pub fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
-        let mut dialog = FileDialog::new()
-            .set_title(self.title)
-            .set_parent(&self.window);
-
-        if let Some(default_filename) = self.default_filename {
-            dialog = dialog.set_file_name(default_filename);
-        }
-
-        for filter in self.filter {
-            dialog = dialog.add_filter(filter.name, filter.extensions);
+    pub(crate) fn show_native(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+        match backend::select(&[
+            Backend::Portal,
+            Backend::Zenity,
+            Backend::Kdialog,
+            Backend::Tty,
+        ])? {
+            Backend::Portal => {
+                let parent = WindowIdentifier::new(&self.window)?;
+                portal::save_file(
+                    &mut Connection::session()?,
+                    parent.as_str(),
+                    self.title,
+                    self.default_filename,
+                    self.filter,
+                    self.starting_directory,
+                )
+            }
+            Backend::Zenity => zenity::save_file(
+                self.title,
+                self.default_filename,
+                self.filter,
+                self.starting_directory,
+            ),
+            Backend::Kdialog => kdialog::save_file(
+                self.title,
+                self.default_filename,
+                self.filter,
+                self.starting_directory,
+            ),
+            Backend::Tty => tty::save_file(
+                self.title,
+                self.default_filename,
+                self.filter,
+                self.starting_directory,
+            ),
         }
-
-        let result = dialog.save_file();
-        Ok(result)
     }
 }
@@ -0,0 +1,223 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::secret::zeroize;
+use crate::{BlockingDialogError, Secret};
+#[cfg(test)]
+use std::cell::RefCell;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fmt::Display;
+use std::io;
+use std::os::unix::ffi::OsStringExt;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+#[cfg(test)]
+thread_local! {
+    /// Stub programs for the current test, searched instead of `PATH`
+    static STUB_PATH: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+pub fn is_available(program: &str) -> bool {
+    let Some(path) = env::var_os("PATH") else {
+        return false;
+    };
+
+    env::split_paths(&path).any(|dir| dir.join(program).is_file())
+}
+
+#[cfg(not(test))]
+fn command(program: &str) -> Command {
+    Command::new(program)
+}
+
+#[cfg(test)]
+fn command(program: &str) -> Command {
+    let mut command = Command::new(program);
+
+    if let Some(path) = STUB_PATH.with_borrow(Clone::clone) {
+        command.env("PATH", path);
+    }
+
+    command
+}
+
+/// Runs the program and returns its exit code and stdout, for dialogs with
+/// more answers than accept and cancel
+pub fn run_with_code<I, S>(program: &str, args: I) -> Result<(i32, Vec<u8>), BlockingDialogError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = command(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()?;
+
+    let Some(code) = output.status.code() else {
+        return Err(exited(program, output.status));
+    };
+
+    let mut stdout = output.stdout;
+    if stdout.last() == Some(&b'\n') {
+        stdout.pop();
+    }
+
+    Ok((code, stdout))
+}
+
+/// Runs the program and returns its stdout, or `None` if the user cancelled
+pub fn run<I, S>(program: &str, args: I) -> Result<Option<Vec<u8>>, BlockingDialogError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    match run_with_code(program, args)? {
+        (0, stdout) => Ok(Some(stdout)),
+        (1, _) => Ok(None),
+        (code, _) => Err(exited(program, code)),
+    }
+}
+
+/// Starts the program with a pipe to its stdin, for dialogs updated while shown
+pub fn spawn<I, S>(program: &str, args: I) -> io::Result<Child>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    command(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+pub fn exited(program: &str, status: impl Display) -> BlockingDialogError {
+    BlockingDialogError::Io(io::Error::other(format!("{program} exited with {status}")))
+}
+
+pub fn parse_paths(stdout: Vec<u8>) -> Vec<PathBuf> {
+    stdout
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| PathBuf::from(OsString::from_vec(line.to_vec())))
+        .collect()
+}
+
+/// Takes the row indices printed one per line by a list dialog, in ascending order
+pub fn parse_indices(stdout: Vec<u8>) -> Vec<usize> {
+    let mut indices = String::from_utf8_lossy(&stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect::<Vec<_>>();
+
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
+/// Takes a password printed by a dialog, wiping the output if it isn't valid UTF-8
+pub fn parse_secret(stdout: Vec<u8>) -> Secret {
+    match String::from_utf8(stdout) {
+        Ok(secret) => Secret::new(secret),
+        Err(err) => {
+            let mut stdout = err.into_bytes();
+            let secret = String::from_utf8_lossy(&stdout).into_owned();
+            zeroize(&mut stdout);
+            Secret::new(secret)
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static STUB_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    /// A shell script standing in for a dialog program, for the current thread only
+    pub struct Stub {
+        dir: PathBuf,
+    }
+
+    impl Stub {
+        /// Prints `stdout` and exits with `code`, recording its arguments
+        pub fn new(program: &str, code: i32, stdout: &str) -> Self {
+            let dir = env::temp_dir().join(format!(
+                "blocking-dialog-stub-{}-{}",
+                std::process::id(),
+                STUB_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(&dir).expect("create the stub directory");
+
+            let quote = |s: &str| format!("'{}'", s.replace('\'', "'\\''"));
+            let script = format!(
+                "#!/bin/sh\nprintf '%s\\000' \"$@\" > {}\nprintf '%s' {}\nexit {code}\n",
+                quote(&dir.join("args").to_string_lossy()),
+                quote(stdout),
+            );
+
+            let path = dir.join(program);
+            fs::write(&path, script).expect("write the stub");
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+                .expect("make the stub executable");
+
+            STUB_PATH.set(Some(dir.clone()));
+            Stub { dir }
+        }
+
+        /// The arguments of the last run
+        pub fn args(&self) -> Vec<String> {
+            let args = fs::read(self.dir.join("args")).expect("the stub wasn't run");
+            let args = String::from_utf8(args).expect("UTF-8 arguments");
+
+            match args.strip_suffix('\0') {
+                Some(args) => args.split('\0').map(str::to_string).collect(),
+                None => Vec::new(),
+            }
+        }
+    }
+
+    impl Drop for Stub {
+        fn drop(&mut self) {
+            STUB_PATH.set(None);
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn maps_exit_codes() {
+        let stub = Stub::new("dialog", 0, "out\n");
+        assert_eq!(run("dialog", ["a b", ""]).unwrap(), Some(b"out".to_vec()));
+        assert_eq!(stub.args(), ["a b", ""]);
+
+        let _stub = Stub::new("dialog", 1, "ignored");
+        assert_eq!(run("dialog", ["x"]).unwrap(), None);
+
+        for code in [2, 5] {
+            let _stub = Stub::new("dialog", code, "");
+            assert!(run("dialog", ["x"]).is_err());
+            assert_eq!(run_with_code("dialog", ["x"]).unwrap(), (code, Vec::new()));
+        }
+    }
+
+    #[test]
+    fn parses_paths() {
+        assert_eq!(
+            parse_paths(b"/tmp/a b.txt\n/tmp/c|d\n\n".to_vec()),
+            [PathBuf::from("/tmp/a b.txt"), PathBuf::from("/tmp/c|d")]
+        );
+        assert!(parse_paths(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn parses_indices() {
+        assert_eq!(parse_indices(b"3\n1\n3\nx\n".to_vec()), [1, 3]);
+    }
+}
@@ -0,0 +1,379 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::mem::MaybeUninit;
+use std::os::fd::AsRawFd;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Interrupt,
+    Char(char),
+    Unknown,
+}
+
+/// The controlling terminal in raw mode, showing the alternate screen
+/// until dropped
+pub struct Terminal {
+    tty: File,
+    original: libc::termios,
+}
+
+impl Terminal {
+    pub fn open() -> io::Result<Self> {
+        let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+        Self::from_tty(tty)
+    }
+
+    fn from_tty(tty: File) -> io::Result<Self> {
+        let fd = tty.as_raw_fd();
+
+        let original = unsafe {
+            let mut termios = MaybeUninit::<libc::termios>::uninit();
+            if libc::tcgetattr(fd, termios.as_mut_ptr()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            termios.assume_init()
+        };
+
+        let mut raw = original;
+        raw.c_iflag &= !(libc::IXON | libc::ICRNL | libc::BRKINT | libc::INPCK | libc::ISTRIP);
+        raw.c_oflag &= !libc::OPOST;
+        raw.c_lflag &= !(libc::ECHO | libc::ICANON | libc::ISIG | libc::IEXTEN);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+
+        if unsafe { libc::tcsetattr(fd, libc::TCSAFLUSH, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut terminal = Terminal { tty, original };
+        terminal.write_all(b"\x1b[?1049h\x1b[?25l")?;
+        terminal.flush()?;
+
+        Ok(terminal)
+    }
+
+    /// Rows and columns, with a conservative default if the size is unknown
+    pub fn size(&self) -> (usize, usize) {
+        let mut size = MaybeUninit::<libc::winsize>::zeroed();
+        let res = unsafe { libc::ioctl(self.tty.as_raw_fd(), libc::TIOCGWINSZ, size.as_mut_ptr()) };
+        let size = unsafe { size.assume_init() };
+
+        if res == 0 && size.ws_row > 0 && size.ws_col > 0 {
+            (size.ws_row as usize, size.ws_col as usize)
+        } else {
+            (24, 80)
+        }
+    }
+
+    fn read_byte(&mut self) -> io::Result<u8> {
+        let mut byte = [0u8; 1];
+        self.tty.read_exact(&mut byte)?;
+        Ok(byte[0])
+    }
+
+    fn has_input(&self, timeout: Duration) -> bool {
+        let mut pollfd = libc::pollfd {
+            fd: self.tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        let timeout = timeout.as_millis().min(i32::MAX as u128) as i32;
+        unsafe { libc::poll(&mut pollfd, 1, timeout) > 0 }
+    }
+
+    /// Whether more input arrives within a few milliseconds, used to tell a
+    /// lone Escape from the start of an escape sequence
+    fn has_pending_input(&self) -> bool {
+        self.has_input(Duration::from_millis(50))
+    }
+
+    /// Like `read_key`, but gives up after `timeout` without input
+    pub fn poll_key(&mut self, timeout: Duration) -> io::Result<Option<Key>> {
+        if !self.has_input(timeout) {
+            return Ok(None);
+        }
+
+        self.read_key().map(Some)
+    }
+
+    pub fn read_key(&mut self) -> io::Result<Key> {
+        let key = match self.read_byte()? {
+            b'\r' | b'\n' => Key::Enter,
+            b'\t' => Key::Tab,
+            0x7f | 0x08 => Key::Backspace,
+            0x03 | 0x04 => Key::Interrupt,
+            0x1b => {
+                if !self.has_pending_input() {
+                    return Ok(Key::Escape);
+                }
+
+                match self.read_byte()? {
+                    b'[' | b'O' => self.read_escape_sequence()?,
+                    _ => Key::Unknown,
+                }
+            }
+            byte if byte < 0x20 => Key::Unknown,
+            byte => {
+                let len = match byte {
+                    0xc0..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    0xf0..=0xf7 => 4,
+                    _ => 1,
+                };
+
+                let mut buf = vec![byte];
+                for _ in 1..len {
+                    buf.push(self.read_byte()?);
+                }
+
+                match std::str::from_utf8(&buf)
+                    .ok()
+                    .and_then(|s| s.chars().next())
+                {
+                    Some(c) => Key::Char(c),
+                    None => Key::Unknown,
+                }
+            }
+        };
+
+        Ok(key)
+    }
+
+    fn read_escape_sequence(&mut self) -> io::Result<Key> {
+        let mut params = Vec::new();
+
+        loop {
+            let byte = self.read_byte()?;
+
+            if byte.is_ascii_digit() || byte == b';' {
+                params.push(byte);
+                continue;
+            }
+
+            let key = match (byte, params.as_slice()) {
+                (b'A', _) => Key::Up,
+                (b'B', _) => Key::Down,
+                (b'C', _) => Key::Right,
+                (b'D', _) => Key::Left,
+                (b'H', _) => Key::Home,
+                (b'F', _) => Key::End,
+                (b'~', b"1" | b"7") => Key::Home,
+                (b'~', b"4" | b"8") => Key::End,
+                (b'~', b"5") => Key::PageUp,
+                (b'~', b"6") => Key::PageDown,
+                _ => Key::Unknown,
+            };
+
+            return Ok(key);
+        }
+    }
+}
+
+impl Write for Terminal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.tty.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.tty.flush()
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = self.tty.write_all(b"\x1b[?25h\x1b[?1049l");
+        let _ = self.tty.flush();
+
+        unsafe {
+            libc::tcsetattr(self.tty.as_raw_fd(), libc::TCSAFLUSH, &self.original);
+        }
+    }
+}
+
+/// Turns off echo on stdin until dropped, leaving line editing alone
+pub struct NoEcho {
+    original: libc::termios,
+}
+
+impl NoEcho {
+    pub fn enable() -> io::Result<Self> {
+        let fd = io::stdin().as_raw_fd();
+
+        let original = unsafe {
+            let mut termios = MaybeUninit::<libc::termios>::uninit();
+            if libc::tcgetattr(fd, termios.as_mut_ptr()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            termios.assume_init()
+        };
+
+        // Still echo the newline so the next output starts on its own line
+        let mut hidden = original;
+        hidden.c_lflag &= !libc::ECHO;
+        hidden.c_lflag |= libc::ECHONL;
+
+        if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &hidden) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(NoEcho { original })
+    }
+}
+
+impl Drop for NoEcho {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(io::stdin().as_raw_fd(), libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Truncates a string to at most `width` characters
+pub fn truncate(s: &str, width: usize) -> &str {
+    match s.char_indices().nth(width) {
+        Some((i, _)) => &s[..i],
+        None => s,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::os::fd::FromRawFd;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    /// Opens a new pseudo-terminal, returning the master and slave ends
+    pub fn open_pty() -> (File, File) {
+        unsafe {
+            let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+            assert!(fd >= 0, "{}", io::Error::last_os_error());
+            let master = File::from_raw_fd(fd);
+
+            assert_eq!(libc::grantpt(master.as_raw_fd()), 0);
+            assert_eq!(libc::unlockpt(master.as_raw_fd()), 0);
+
+            let mut name = [0 as libc::c_char; 128];
+            let res = libc::ptsname_r(master.as_raw_fd(), name.as_mut_ptr(), name.len());
+            assert_eq!(res, 0);
+            let name = std::ffi::CStr::from_ptr(name.as_ptr()).to_str().unwrap();
+
+            let slave = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .custom_flags(libc::O_NOCTTY)
+                .open(name)
+                .unwrap();
+
+            (master, slave)
+        }
+    }
+
+    /// A terminal on a new pseudo-terminal, and the master end to type into
+    pub fn open_terminal() -> (File, Terminal) {
+        let (master, slave) = open_pty();
+        (master, Terminal::from_tty(slave).unwrap())
+    }
+
+    fn read_keys(input: &[u8], count: usize) -> Vec<Key> {
+        let (mut master, mut terminal) = open_terminal();
+        master.write_all(input).unwrap();
+
+        (0..count).map(|_| terminal.read_key().unwrap()).collect()
+    }
+
+    #[test]
+    fn reads_plain_keys() {
+        let keys = read_keys(b"a\r\n\t\x7f\x08\x03\x04\x01", 9);
+        let expected = [
+            Key::Char('a'),
+            Key::Enter,
+            Key::Enter,
+            Key::Tab,
+            Key::Backspace,
+            Key::Backspace,
+            Key::Interrupt,
+            Key::Interrupt,
+            Key::Unknown,
+        ];
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn reads_utf8_characters() {
+        let keys = read_keys("é€🦀".as_bytes(), 3);
+        assert_eq!(keys, [Key::Char('é'), Key::Char('€'), Key::Char('🦀')]);
+
+        assert_eq!(read_keys(b"\xff", 1), [Key::Unknown]);
+    }
+
+    #[test]
+    fn reads_escape_sequences() {
+        let input = b"\x1b[A\x1b[B\x1b[C\x1b[D\x1bOA\x1b[H\x1b[F\x1bOH\x1b[1~\x1b[7~\x1b[4~\x1b[8~\x1b[5~\x1b[6~\x1b[3~\x1b[1;5A\x1bx";
+        let expected = [
+            Key::Up,
+            Key::Down,
+            Key::Right,
+            Key::Left,
+            Key::Up,
+            Key::Home,
+            Key::End,
+            Key::Home,
+            Key::Home,
+            Key::Home,
+            Key::End,
+            Key::End,
+            Key::PageUp,
+            Key::PageDown,
+            Key::Unknown,
+            Key::Up,
+            Key::Unknown,
+        ];
+        assert_eq!(read_keys(input, expected.len()), expected);
+    }
+
+    #[test]
+    fn reads_a_lone_escape() {
+        let (mut master, mut terminal) = open_terminal();
+
+        master.write_all(b"\x1b").unwrap();
+        assert_eq!(terminal.read_key().unwrap(), Key::Escape);
+
+        master.write_all(b"q").unwrap();
+        assert_eq!(terminal.read_key().unwrap(), Key::Char('q'));
+    }
+
+    #[test]
+    fn polls_for_keys() {
+        let (mut master, mut terminal) = open_terminal();
+
+        let timeout = Duration::from_millis(10);
+        assert_eq!(terminal.poll_key(timeout).unwrap(), None);
+
+        master.write_all(b"\x1b[6~").unwrap();
+        assert_eq!(terminal.poll_key(timeout).unwrap(), Some(Key::PageDown));
+    }
+
+    #[test]
+    fn truncates_characters() {
+        assert_eq!(truncate("héllo", 2), "hé");
+        assert_eq!(truncate("héllo", 10), "héllo");
+        assert_eq!(truncate("", 0), "");
+    }
+}
@@ -0,0 +1,1136 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::browser::{self, Mode};
+use super::date;
+use super::menu;
+use super::progress_bar;
+use super::rgba;
+use super::terminal::NoEcho;
+use crate::progress::POLL_INTERVAL;
+use crate::secret::zeroize;
+use crate::{
+    BlockingDialogError, BlockingDialogLevel, BlockingFormDialogField, BlockingFormDialogFieldKind,
+    BlockingFormDialogValue, BlockingListDialogMode, BlockingPickFilesDialogFilter,
+    BlockingQuestionDialogResponse, Credentials, Date, Progress, Rgba, Secret,
+};
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::mem::ManuallyDrop;
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+pub fn is_available() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Whether the terminal can show the full-screen browser and menu, otherwise input is typed
+fn has_browser() -> bool {
+    env::var("TERM").is_ok_and(|term| !term.is_empty() && term != "dumb")
+}
+
+fn get_prefix(level: BlockingDialogLevel) -> &'static str {
+    match level {
+        BlockingDialogLevel::Info => "Info",
+        BlockingDialogLevel::Warning => "Warning",
+        BlockingDialogLevel::Error => "Error",
+    }
+}
+
+/// Prints the prompt on stderr and reads a line, `None` on EOF
+fn prompt(prompt: &str) -> Result<Option<String>, BlockingDialogError> {
+    let mut stderr = io::stderr().lock();
+    write!(stderr, "{prompt}")?;
+    stderr.flush()?;
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        writeln!(stderr)?;
+        return Ok(None);
+    }
+
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+fn print_header(title: &str) -> Result<(), BlockingDialogError> {
+    if !title.is_empty() {
+        writeln!(io::stderr(), "{title}")?;
+    }
+
+    Ok(())
+}
+
+/// Resolves a typed path, relative ones against the starting directory if any
+fn expand_path(input: &str, starting_directory: Option<&Path>) -> io::Result<PathBuf> {
+    let path = match input.strip_prefix("~/") {
+        Some(rest) => match env::var_os("HOME") {
+            Some(home) => Path::new(&home).join(rest),
+            None => PathBuf::from(input),
+        },
+        None => PathBuf::from(input),
+    };
+
+    match starting_directory {
+        Some(dir) => std::path::absolute(dir.join(path)),
+        None => std::path::absolute(path),
+    }
+}
+
+pub fn matches_filter(path: &Path, filter: &[BlockingPickFilesDialogFilter]) -> bool {
+    if filter.is_empty() {
+        return true;
+    }
+
+    let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
+        return false;
+    };
+
+    filter
+        .iter()
+        .flat_map(|entry| entry.extensions)
+        .any(|allowed| allowed.eq_ignore_ascii_case(ext))
+}
+
+fn describe_filter(filter: &[BlockingPickFilesDialogFilter]) -> String {
+    filter
+        .iter()
+        .map(|entry| {
+            let patterns = entry
+                .extensions
+                .iter()
+                .map(|ext| format!("*.{ext}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            format!("{} ({})", entry.name, patterns)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn alert(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+) -> Result<(), BlockingDialogError> {
+    let prefix = get_prefix(level);
+    writeln!(io::stderr(), "{prefix}: {title}\n{message}")?;
+
+    Ok(())
+}
+
+pub fn confirm(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+    accept_label: Option<&str>,
+    cancel_label: Option<&str>,
+) -> Result<bool, BlockingDialogError> {
+    let prefix = get_prefix(level);
+    writeln!(io::stderr(), "{prefix}: {title}\n{message}")?;
+
+    let label = match (accept_label, cancel_label) {
+        (None, None) => "[y/N] ".to_string(),
+        _ => format!(
+            "{} [y] / {} [N] ",
+            accept_label.unwrap_or("Yes"),
+            cancel_label.unwrap_or("No")
+        ),
+    };
+
+    let answer = prompt(&label)?.unwrap_or_default();
+    let answer = answer.trim();
+
+    Ok(answer.eq_ignore_ascii_case("y")
+        || answer.eq_ignore_ascii_case("yes")
+        || accept_label.is_some_and(|label| answer.eq_ignore_ascii_case(label)))
+}
+
+pub fn question(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+    let prefix = get_prefix(level);
+    writeln!(io::stderr(), "{prefix}: {title}\n{message}")?;
+
+    let answer = prompt("[y/n/C] ")?.unwrap_or_default();
+    let answer = answer.trim();
+
+    let response = if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") {
+        BlockingQuestionDialogResponse::Yes
+    } else if answer.eq_ignore_ascii_case("n") || answer.eq_ignore_ascii_case("no") {
+        BlockingQuestionDialogResponse::No
+    } else {
+        BlockingQuestionDialogResponse::Cancel
+    };
+
+    Ok(response)
+}
+
+pub fn message(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+    buttons: &[&str],
+    default_button: usize,
+    cancel_button: usize,
+) -> Result<usize, BlockingDialogError> {
+    let prefix = get_prefix(level);
+    writeln!(io::stderr(), "{prefix}: {title}\n{message}")?;
+
+    for (i, button) in buttons.iter().enumerate() {
+        writeln!(io::stderr(), "  {}) {button}", i + 1)?;
+    }
+
+    let label = format!("Choice [{}]: ", default_button + 1);
+
+    loop {
+        let Some(answer) = prompt(&label)? else {
+            return Ok(cancel_button);
+        };
+
+        let answer = answer.trim();
+
+        if answer.is_empty() {
+            return Ok(default_button);
+        }
+
+        let index = match answer.parse::<usize>() {
+            Ok(n) => n.checked_sub(1).filter(|i| *i < buttons.len()),
+            Err(_) => buttons
+                .iter()
+                .position(|button| button.eq_ignore_ascii_case(answer)),
+        };
+
+        if let Some(index) = index {
+            return Ok(index);
+        }
+
+        writeln!(io::stderr(), "Enter a number from 1 to {}", buttons.len())?;
+    }
+}
+
+pub fn input(
+    title: &str,
+    message: &str,
+    default_text: Option<&str>,
+    placeholder: Option<&str>,
+) -> Result<Option<String>, BlockingDialogError> {
+    print_header(title)?;
+
+    if !message.is_empty() {
+        writeln!(io::stderr(), "{message}")?;
+    }
+
+    // An empty line keeps the default, the placeholder is only a hint
+    let label = match (default_text, placeholder) {
+        (Some(default_text), _) => format!("[{default_text}]: "),
+        (None, Some(placeholder)) => format!("({placeholder}): "),
+        (None, None) => "> ".to_string(),
+    };
+
+    let Some(input) = prompt(&label)? else {
+        return Ok(None);
+    };
+
+    match (input.is_empty(), default_text) {
+        (true, Some(default_text)) => Ok(Some(default_text.to_string())),
+        _ => Ok(Some(input)),
+    }
+}
+
+pub fn password(title: &str, message: &str) -> Result<Option<Secret>, BlockingDialogError> {
+    print_header(title)?;
+
+    let label = if message.is_empty() {
+        "Password"
+    } else {
+        message
+    };
+    let mut stderr = io::stderr().lock();
+    write!(stderr, "{label}: ")?;
+    stderr.flush()?;
+
+    let line = {
+        let _no_echo = NoEcho::enable()?;
+        read_secret_line()?
+    };
+
+    if line.is_none() {
+        writeln!(stderr)?;
+    }
+
+    Ok(line)
+}
+
+/// Reads a line straight from stdin, so no buffer outlives it but the `Secret`, `None` on EOF
+fn read_secret_line() -> Result<Option<Secret>, BlockingDialogError> {
+    // Not owned, stdin must stay open
+    let mut stdin = ManuallyDrop::new(unsafe { File::from_raw_fd(io::stdin().as_raw_fd()) });
+
+    // The terminal hands out lines of at most this many bytes
+    let mut buf = vec![0; 4096];
+    let mut len = 0;
+
+    let read = loop {
+        match stdin.read(&mut buf[len..]) {
+            Ok(0) => break Ok(len > 0),
+            Ok(n) => {
+                len += n;
+
+                if buf[len - 1] == b'\n' || len == buf.len() {
+                    break Ok(true);
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => break Err(err),
+        }
+    };
+
+    let mut line = &buf[..len];
+    while let [rest @ .., b'\r' | b'\n'] = line {
+        line = rest;
+    }
+
+    // Invalid bytes take at most 3 each once replaced, so the string never grows
+    let mut secret = String::with_capacity(3 * line.len());
+    for chunk in line.utf8_chunks() {
+        secret.push_str(chunk.valid());
+
+        if !chunk.invalid().is_empty() {
+            secret.push(char::REPLACEMENT_CHARACTER);
+        }
+    }
+
+    zeroize(&mut buf);
+    let secret = Secret::new(secret);
+
+    Ok(read?.then_some(secret))
+}
+
+pub fn login(
+    title: &str,
+    message: &str,
+    username: Option<&str>,
+) -> Result<Option<Credentials>, BlockingDialogError> {
+    print_header(title)?;
+
+    if !message.is_empty() {
+        writeln!(io::stderr(), "{message}")?;
+    }
+
+    let label = match username {
+        Some(username) => format!("Username [{username}]: "),
+        None => "Username: ".to_string(),
+    };
+
+    let Some(input) = prompt(&label)? else {
+        return Ok(None);
+    };
+
+    let username = match (input.is_empty(), username) {
+        (true, Some(username)) => username.to_string(),
+        _ => input,
+    };
+
+    let Some(password) = password("", "Password")? else {
+        return Ok(None);
+    };
+
+    Ok(Some(Credentials { username, password }))
+}
+
+/// Asks for each field in turn, EOF at any point cancels the whole form
+pub fn form(
+    title: &str,
+    message: &str,
+    fields: &[BlockingFormDialogField],
+) -> Result<Option<HashMap<String, BlockingFormDialogValue>>, BlockingDialogError> {
+    print_header(title)?;
+
+    if !message.is_empty() {
+        writeln!(io::stderr(), "{message}")?;
+    }
+
+    let mut values = HashMap::new();
+
+    for field in fields {
+        let Some(value) = form_field(field)? else {
+            return Ok(None);
+        };
+
+        values.insert(field.id.to_string(), value);
+    }
+
+    Ok(Some(values))
+}
+
+fn form_field(
+    field: &BlockingFormDialogField,
+) -> Result<Option<BlockingFormDialogValue>, BlockingDialogError> {
+    let label = field.label;
+
+    let value = match field.kind {
+        BlockingFormDialogFieldKind::Text => {
+            prompt(&format!("{label}: "))?.map(BlockingFormDialogValue::Text)
+        }
+        BlockingFormDialogFieldKind::Password => {
+            password("", label)?.map(BlockingFormDialogValue::Password)
+        }
+        BlockingFormDialogFieldKind::Multiline => {
+            writeln!(io::stderr(), "{label} (an empty line to finish):")?;
+
+            let mut lines = Vec::new();
+            loop {
+                match prompt("")? {
+                    Some(line) if line.is_empty() => break,
+                    Some(line) => lines.push(line),
+                    None => return Ok(None),
+                }
+            }
+
+            Some(BlockingFormDialogValue::Text(lines.join("\n")))
+        }
+        BlockingFormDialogFieldKind::Dropdown(options) => {
+            writeln!(io::stderr(), "{label}:")?;
+
+            for (i, option) in options.iter().enumerate() {
+                writeln!(io::stderr(), "  {}) {option}", i + 1)?;
+            }
+
+            loop {
+                let Some(answer) = prompt("Choice [1]: ")? else {
+                    return Ok(None);
+                };
+
+                let answer = answer.trim();
+
+                let index = match answer.parse::<usize>() {
+                    _ if answer.is_empty() => Some(0),
+                    Ok(n) => n.checked_sub(1).filter(|i| *i < options.len()),
+                    Err(_) => options.iter().position(|o| o.eq_ignore_ascii_case(answer)),
+                };
+
+                if let Some(index) = index {
+                    break Some(BlockingFormDialogValue::Dropdown(index));
+                }
+
+                writeln!(io::stderr(), "Enter a number from 1 to {}", options.len())?;
+            }
+        }
+        BlockingFormDialogFieldKind::Checkbox => {
+            prompt(&format!("{label} [y/N]: "))?.map(|answer| {
+                let answer = answer.trim();
+                let checked =
+                    answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes");
+                BlockingFormDialogValue::Checkbox(checked)
+            })
+        }
+        BlockingFormDialogFieldKind::Date => {
+            let (year, month, day) = date::today();
+            let label = format!("{label} [{year:04}-{month:02}-{day:02}]: ");
+
+            loop {
+                let Some(answer) = prompt(&label)? else {
+                    return Ok(None);
+                };
+
+                if answer.trim().is_empty() {
+                    break Some(BlockingFormDialogValue::Date(year, month, day));
+                }
+
+                if let Some((year, month, day)) = date::parse(&answer) {
+                    break Some(BlockingFormDialogValue::Date(year, month, day));
+                }
+
+                writeln!(io::stderr(), "Enter a date as YYYY-MM-DD")?;
+            }
+        }
+    };
+
+    Ok(value)
+}
+
+pub fn list(
+    title: &str,
+    message: &str,
+    columns: &[&str],
+    rows: &[&[&str]],
+    selected: Vec<usize>,
+    mode: BlockingListDialogMode,
+) -> Result<Vec<usize>, BlockingDialogError> {
+    if has_browser() {
+        return menu::run(title, message, columns, rows, selected, mode);
+    }
+
+    print_header(title)?;
+
+    if !message.is_empty() {
+        writeln!(io::stderr(), "{message}")?;
+    }
+
+    let (header, rows) = menu::format_rows(columns, rows);
+
+    if !header.is_empty() {
+        writeln!(io::stderr(), "      {header}")?;
+    }
+
+    for (i, row) in rows.iter().enumerate() {
+        writeln!(io::stderr(), "  {:>2}) {row}", i + 1)?;
+    }
+
+    let multiple = mode == BlockingListDialogMode::Checklist;
+    let default = selected
+        .iter()
+        .map(|i| (i + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // An empty line keeps the preselection
+    let label = match (multiple, default.is_empty()) {
+        (true, true) => "Choices (space separated): ".to_string(),
+        (true, false) => format!("Choices (space separated) [{default}]: "),
+        (false, true) => "Choice: ".to_string(),
+        (false, false) => format!("Choice [{default}]: "),
+    };
+
+    loop {
+        let Some(answer) = prompt(&label)? else {
+            return Ok(Vec::new());
+        };
+
+        if answer.trim().is_empty() {
+            return Ok(selected);
+        }
+
+        let indices = answer
+            .split_whitespace()
+            .map(|n| {
+                n.parse::<usize>()
+                    .ok()
+                    .and_then(|n| n.checked_sub(1))
+                    .filter(|i| *i < rows.len())
+            })
+            .collect::<Option<Vec<_>>>();
+
+        match indices {
+            Some(mut indices) if multiple || indices.len() == 1 => {
+                indices.sort_unstable();
+                indices.dedup();
+                return Ok(indices);
+            }
+            _ => writeln!(io::stderr(), "Enter a number from 1 to {}", rows.len())?,
+        }
+    }
+}
+
+pub fn color(
+    title: &str,
+    initial: Option<Rgba>,
+    alpha: bool,
+) -> Result<Option<Rgba>, BlockingDialogError> {
+    print_header(title)?;
+
+    let format = if alpha { "#rrggbbaa" } else { "#rrggbb" };
+
+    // An empty line keeps the initial color
+    let label = match initial {
+        Some(initial) => format!("Color ({format}) [{}]: ", rgba::to_hex(initial, alpha)),
+        None => format!("Color ({format}): "),
+    };
+
+    loop {
+        let Some(answer) = prompt(&label)? else {
+            return Ok(None);
+        };
+
+        if answer.trim().is_empty()
+            && let Some(initial) = initial
+        {
+            return Ok(Some(initial));
+        }
+
+        if let Some(color) = rgba::parse(&answer) {
+            return Ok(Some(color));
+        }
+
+        writeln!(io::stderr(), "Enter a color as {format} or rgb(r, g, b)")?;
+    }
+}
+
+pub fn date(
+    title: &str,
+    message: &str,
+    initial: Date,
+    min: Option<Date>,
+    max: Option<Date>,
+) -> Result<Option<Date>, BlockingDialogError> {
+    print_header(title)?;
+
+    if !message.is_empty() {
+        writeln!(io::stderr(), "{message}")?;
+    }
+
+    // An empty line keeps the initial date
+    let label = format!("Date (YYYY-MM-DD) [{}]: ", date::format(initial));
+
+    loop {
+        let Some(answer) = prompt(&label)? else {
+            return Ok(None);
+        };
+
+        if answer.trim().is_empty() {
+            return Ok(Some(initial));
+        }
+
+        let Some((year, month, day)) = date::parse(&answer) else {
+            writeln!(io::stderr(), "Enter a date as YYYY-MM-DD")?;
+            continue;
+        };
+
+        let date = Date::new(year, month, day);
+        if min.is_some_and(|min| date < min) || max.is_some_and(|max| date > max) {
+            writeln!(io::stderr(), "{}", date::describe_range(min, max))?;
+            continue;
+        }
+
+        return Ok(Some(date));
+    }
+}
+
+/// Without a full-screen terminal each change is printed on its own line,
+/// and there's no way to cancel
+pub fn progress(
+    title: &str,
+    message: &str,
+    progress: &Progress,
+) -> Result<(), BlockingDialogError> {
+    if has_browser() {
+        return progress_bar::run(title, message, progress);
+    }
+
+    print_header(title)?;
+
+    if !message.is_empty() {
+        writeln!(io::stderr(), "{message}")?;
+    }
+
+    let mut shown = None;
+
+    while !progress.is_finished() {
+        let state = progress.get_state();
+
+        if shown.as_ref() != Some(&state) {
+            let bar = progress_bar::get_bar(state.fraction, 0, 40);
+
+            match &state.text {
+                Some(text) => writeln!(io::stderr(), "{bar} {text}")?,
+                None => writeln!(io::stderr(), "{bar}")?,
+            }
+
+            shown = Some(state);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    Ok(())
+}
+
+pub fn pick_files(
+    title: &str,
+    multiple: bool,
+    filter: &[BlockingPickFilesDialogFilter],
+    starting_directory: Option<&Path>,
+) -> Result<Vec<PathBuf>, BlockingDialogError> {
+    if has_browser() {
+        return browser::run(title, Mode::Open { multiple }, filter, starting_directory);
+    }
+
+    print_header(title)?;
+
+    if !filter.is_empty() {
+        writeln!(io::stderr(), "Allowed: {}", describe_filter(filter))?;
+    }
+
+    if multiple {
+        writeln!(
+            io::stderr(),
+            "Enter one path per line, an empty line to finish"
+        )?;
+    }
+
+    let mut paths = Vec::new();
+
+    loop {
+        let Some(input) = prompt("File: ")? else {
+            return Ok(Vec::new());
+        };
+
+        if input.is_empty() {
+            return Ok(paths);
+        }
+
+        let path = expand_path(&input, starting_directory)?;
+
+        if !path.is_file() {
+            writeln!(io::stderr(), "Not a file: {}", path.display())?;
+        } else if !matches_filter(&path, filter) {
+            writeln!(io::stderr(), "Not an allowed file type: {}", path.display())?;
+        } else {
+            paths.push(path);
+
+            if !multiple {
+                return Ok(paths);
+            }
+        }
+    }
+}
+
+pub fn save_file(
+    title: &str,
+    default_filename: Option<&str>,
+    filter: &[BlockingPickFilesDialogFilter],
+    starting_directory: Option<&Path>,
+) -> Result<Option<PathBuf>, BlockingDialogError> {
+    if has_browser() {
+        let mode = Mode::Save { default_filename };
+        let mut paths = browser::run(title, mode, filter, starting_directory)?;
+        return Ok(paths.pop());
+    }
+
+    print_header(title)?;
+
+    if !filter.is_empty() {
+        writeln!(io::stderr(), "Allowed: {}", describe_filter(filter))?;
+    }
+
+    let label = match default_filename {
+        Some(default_filename) => format!("Save as [{default_filename}]: "),
+        None => "Save as: ".to_string(),
+    };
+
+    loop {
+        let Some(input) = prompt(&label)? else {
+            return Ok(None);
+        };
+
+        let input = match (input.is_empty(), default_filename) {
+            (false, _) => input,
+            (true, Some(default_filename)) => default_filename.to_string(),
+            (true, None) => return Ok(None),
+        };
+
+        let path = expand_path(&input, starting_directory)?;
+
+        if path.is_dir() {
+            writeln!(io::stderr(), "Is a directory: {}", path.display())?;
+        } else if !path.parent().is_some_and(Path::is_dir) {
+            writeln!(io::stderr(), "No such directory: {}", path.display())?;
+        } else if !matches_filter(&path, filter) {
+            writeln!(io::stderr(), "Not an allowed file type: {}", path.display())?;
+        } else if path.exists() {
+            let overwrite = prompt(&format!("{} exists, overwrite? [y/N] ", path.display()))?
+                .is_some_and(|answer| answer.trim().eq_ignore_ascii_case("y"));
+
+            if overwrite {
+                return Ok(Some(path));
+            }
+        } else {
+            return Ok(Some(path));
+        }
+    }
+}
+
+pub fn pick_directory(
+    title: &str,
+    starting_directory: Option<&Path>,
+) -> Result<Option<PathBuf>, BlockingDialogError> {
+    if has_browser() {
+        let mut paths = browser::run(title, Mode::Directory, &[], starting_directory)?;
+        return Ok(paths.pop());
+    }
+
+    print_header(title)?;
+
+    loop {
+        let Some(input) = prompt("Directory: ")? else {
+            return Ok(None);
+        };
+
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let path = expand_path(&input, starting_directory)?;
+
+        if path.is_dir() {
+            return Ok(Some(path));
+        }
+
+        writeln!(io::stderr(), "Not a directory: {}", path.display())?;
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::os_dialog::terminal::tests::open_pty;
+    use std::fmt::Debug;
+    use std::fs::{self, File};
+    use std::io::Read;
+    use std::os::unix::process::CommandExt;
+    use std::process::{Child, Command, Stdio};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc::{self, Receiver};
+    use std::time::{Duration, Instant};
+
+    /// Set in the child process a test is rerun in
+    const CHILD_VAR: &str = "BLOCKING_DIALOG_PTY_CHILD";
+    const RESULT_PREFIX: &str = "pty result: ";
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    static DIR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    const FILTER: &[BlockingPickFilesDialogFilter] = &[BlockingPickFilesDialogFilter {
+        name: "Text",
+        extensions: &["txt"],
+    }];
+
+    /// In the child process, runs the dialog and reports its result,
+    /// returning whether it did
+    pub fn run_in_child<T: Debug>(dialog: impl FnOnce() -> T) -> bool {
+        if env::var_os(CHILD_VAR).is_none() {
+            return false;
+        }
+
+        let result = dialog();
+        let mut stdout = io::stdout().lock();
+        writeln!(stdout, "{RESULT_PREFIX}{result:?}").unwrap();
+        stdout.flush().unwrap();
+
+        true
+    }
+
+    /// A fresh directory, removed when dropped
+    pub struct TempDir {
+        path: PathBuf,
+    }
+
+    impl TempDir {
+        /// Creates `files` in the directory, names ending in `/` as directories
+        pub fn new(files: &[&str]) -> Self {
+            let path = env::temp_dir().join(format!(
+                "blocking-dialog-test-{}-{}",
+                std::process::id(),
+                DIR_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(&path).unwrap();
+            let path = fs::canonicalize(path).unwrap();
+
+            for file in files {
+                match file.strip_suffix('/') {
+                    Some(name) => fs::create_dir(path.join(name)).unwrap(),
+                    None => fs::write(path.join(file), "").unwrap(),
+                }
+            }
+
+            TempDir { path }
+        }
+
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    /// A test rerun in a child process whose controlling terminal, stdin and
+    /// stderr are a new pseudo-terminal, in a fresh working directory
+    pub struct Pty {
+        master: File,
+        output: Receiver<Vec<u8>>,
+        screen: Vec<u8>,
+        child: Child,
+        dir: TempDir,
+    }
+
+    impl Pty {
+        /// Reruns the test at `path`, creating `files` in its working directory
+        pub fn spawn(path: &str, term: &str, files: &[&str]) -> Self {
+            let dir = TempDir::new(files);
+            let (master, slave) = open_pty();
+
+            // Test names don't include the crate
+            let name = path.split_once("::").map_or(path, |(_, name)| name);
+            let mut command = Command::new(env::current_exe().unwrap());
+            command
+                .args([name, "--exact"])
+                .current_dir(dir.path())
+                .env(CHILD_VAR, "1")
+                .env("TERM", term)
+                .stdin(slave.try_clone().unwrap())
+                .stdout(Stdio::piped())
+                .stderr(slave);
+
+            unsafe {
+                command.pre_exec(|| {
+                    if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY, 0) == -1 {
+                        return Err(io::Error::last_os_error());
+                    }
+
+                    Ok(())
+                });
+            }
+
+            let child = command.spawn().unwrap();
+            drop(command);
+
+            let (sender, output) = mpsc::channel();
+            let mut reader = master.try_clone().unwrap();
+            thread::spawn(move || {
+                let mut buf = [0; 1024];
+
+                // Reading fails with EIO once the child exits
+                while let Ok(len @ 1..) = reader.read(&mut buf) {
+                    if sender.send(buf[..len].to_vec()).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            Pty {
+                master,
+                output,
+                screen: Vec::new(),
+                child,
+                dir,
+            }
+        }
+
+        pub fn dir(&self) -> &Path {
+            self.dir.path()
+        }
+
+        /// Waits until the child prints `text`, skipping everything before it
+        pub fn expect(&mut self, text: &str) {
+            let deadline = Instant::now() + TIMEOUT;
+
+            loop {
+                if let Some(pos) = self
+                    .screen
+                    .windows(text.len())
+                    .position(|window| window == text.as_bytes())
+                {
+                    self.screen.drain(..pos + text.len());
+                    return;
+                }
+
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                match self.output.recv_timeout(timeout) {
+                    Ok(chunk) => self.screen.extend(chunk),
+                    Err(_) => panic!(
+                        "expected {text:?}, got {:?}",
+                        String::from_utf8_lossy(&self.screen)
+                    ),
+                }
+            }
+        }
+
+        pub fn send(&mut self, input: &str) {
+            self.master.write_all(input.as_bytes()).unwrap();
+        }
+
+        /// Waits for the child to exit and returns the result it reported
+        pub fn result(mut self) -> String {
+            let deadline = Instant::now() + TIMEOUT;
+
+            while self.child.try_wait().unwrap().is_none() {
+                assert!(Instant::now() < deadline, "the child didn't exit");
+                thread::sleep(Duration::from_millis(10));
+            }
+
+            let mut stdout = String::new();
+            let pipe = self.child.stdout.as_mut().unwrap();
+            pipe.read_to_string(&mut stdout).unwrap();
+
+            // libtest prints the test name on the same line
+            let Some((_, result)) = stdout.split_once(RESULT_PREFIX) else {
+                panic!("the child reported nothing: {stdout}");
+            };
+
+            result.lines().next().unwrap_or_default().to_string()
+        }
+    }
+
+    impl Drop for Pty {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    fn spawn(path: &str, files: &[&str]) -> Pty {
+        Pty::spawn(path, "dumb", files)
+    }
+
+    #[test]
+    fn confirms_no_by_default() {
+        if run_in_child(|| {
+            confirm(
+                "Delete",
+                "Delete it?",
+                BlockingDialogLevel::Warning,
+                None,
+                None,
+            )
+        }) {
+            return;
+        }
+
+        let mut pty = spawn(concat!(module_path!(), "::confirms_no_by_default"), &[]);
+        pty.expect("Warning: Delete");
+        pty.expect("[y/N] ");
+        pty.send("\n");
+        assert_eq!(pty.result(), "Ok(false)");
+    }
+
+    #[test]
+    fn confirms_with_labels() {
+        let dialog = || {
+            let level = BlockingDialogLevel::Info;
+            confirm("Delete", "Delete it?", level, Some("Delete"), Some("Keep"))
+        };
+        if run_in_child(dialog) {
+            return;
+        }
+
+        let mut pty = spawn(concat!(module_path!(), "::confirms_with_labels"), &[]);
+        pty.expect("Delete [y] / Keep [N] ");
+        pty.send("delete\n");
+        assert_eq!(pty.result(), "Ok(true)");
+    }
+
+    #[test]
+    fn logs_in() {
+        let dialog = || {
+            let credentials = login("Sign in", "", Some("alice"))?;
+            Ok::<_, BlockingDialogError>(
+                credentials.map(|c| (c.username, c.password.expose().to_string())),
+            )
+        };
+        if run_in_child(dialog) {
+            return;
+        }
+
+        let mut pty = spawn(concat!(module_path!(), "::logs_in"), &[]);
+        pty.expect("Username [alice]: ");
+        pty.send("\n");
+        pty.expect("Password: ");
+        pty.send("pa ss \n");
+        assert_eq!(pty.result(), r#"Ok(Some(("alice", "pa ss ")))"#);
+    }
+
+    #[test]
+    fn prompts_again_for_a_directory() {
+        if run_in_child(|| pick_directory("Open", None)) {
+            return;
+        }
+
+        let mut pty = spawn(
+            concat!(module_path!(), "::prompts_again_for_a_directory"),
+            &["notes.txt", "sub/"],
+        );
+        pty.expect("Open");
+        pty.expect("Directory: ");
+        pty.send("missing\n");
+        pty.expect("Not a directory: ");
+        pty.expect("Directory: ");
+        pty.send("notes.txt\n");
+        pty.expect("Not a directory: ");
+        pty.expect("Directory: ");
+        pty.send("sub\n");
+
+        let expected = format!("Ok(Some({:?}))", pty.dir().join("sub"));
+        assert_eq!(pty.result(), expected);
+    }
+
+    #[test]
+    fn checks_typed_paths_against_the_filter() {
+        if run_in_child(|| pick_files("Open", true, FILTER, None)) {
+            return;
+        }
+
+        let mut pty = spawn(
+            concat!(module_path!(), "::checks_typed_paths_against_the_filter"),
+            &["notes.txt", "README.TXT", "image.png"],
+        );
+        pty.expect("Allowed: Text (*.txt)");
+        pty.expect("File: ");
+        pty.send("image.png\n");
+        pty.expect("Not an allowed file type: ");
+        pty.expect("File: ");
+        pty.send("notes.txt\n");
+        pty.expect("File: ");
+        pty.send("README.TXT\n");
+        pty.expect("File: ");
+        pty.send("\n");
+
+        let paths = [pty.dir().join("notes.txt"), pty.dir().join("README.TXT")];
+        assert_eq!(pty.result(), format!("Ok({paths:?})"));
+    }
+
+    #[test]
+    fn saves_only_allowed_file_types() {
+        if run_in_child(|| save_file("Save", Some("notes.txt"), FILTER, None)) {
+            return;
+        }
+
+        let mut pty = spawn(
+            concat!(module_path!(), "::saves_only_allowed_file_types"),
+            &["notes.txt"],
+        );
+        pty.expect("Save as [notes.txt]: ");
+        pty.send("image.png\n");
+        pty.expect("Not an allowed file type: ");
+        pty.expect("Save as [notes.txt]: ");
+        pty.send("\n");
+        pty.expect("exists, overwrite? [y/N] ");
+        pty.send("y\n");
+
+        let expected = format!("Ok(Some({:?}))", pty.dir().join("notes.txt"));
+        assert_eq!(pty.result(), expected);
+    }
+
+    #[test]
+    fn cancels_on_eof() {
+        if run_in_child(|| pick_files("Open", false, &[], None)) {
+            return;
+        }
+
+        let mut pty = spawn(concat!(module_path!(), "::cancels_on_eof"), &["notes.txt"]);
+        pty.expect("File: ");
+        pty.send("\x04");
+        assert_eq!(pty.result(), "Ok([])");
+    }
+
+    #[test]
+    fn cancels_saving_on_eof() {
+        if run_in_child(|| save_file("Save", Some("notes.txt"), &[], None)) {
+            return;
+        }
+
+        let mut pty = spawn(concat!(module_path!(), "::cancels_saving_on_eof"), &[]);
+        pty.expect("Save as [notes.txt]: ");
+        pty.send("\x04");
+        assert_eq!(pty.result(), "Ok(None)");
+    }
+}
@@ -0,0 +1,167 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::BlockingDialogError;
+use raw_window_handle::{
+    HandleError, HasDisplayHandle, HasWindowHandle, RawDisplayHandle, RawWindowHandle,
+};
+use std::ffi::{c_ulong, c_void};
+use wayland_backend::sys::client::{Backend, ObjectId};
+use wayland_client::protocol::{wl_registry, wl_surface::WlSurface};
+use wayland_client::{Connection, Dispatch, Proxy, QueueHandle};
+use wayland_protocols::xdg::foreign::zv2::client::{
+    zxdg_exported_v2::{self, ZxdgExportedV2},
+    zxdg_exporter_v2::ZxdgExporterV2,
+};
+
+/// The `parent_window` string the portals expect, see
+/// <https://flatpak.github.io/xdg-desktop-portal/docs/window-identifiers.html>
+pub struct WindowIdentifier {
+    handle: String,
+    exported: Option<(Connection, ZxdgExportedV2)>,
+}
+
+impl WindowIdentifier {
+    pub fn new<W: HasWindowHandle + HasDisplayHandle>(
+        window: &W,
+    ) -> Result<Self, BlockingDialogError> {
+        let w = match window.window_handle() {
+            Ok(w) => w,
+            Err(HandleError::Unavailable) => return Ok(Self::empty()),
+            Err(err) => return Err(BlockingDialogError::Handle(err)),
+        };
+
+        match w.as_raw() {
+            RawWindowHandle::Xlib(handle) => Ok(Self::x11(handle.window)),
+            RawWindowHandle::Xcb(handle) => Ok(Self::x11(handle.window.get().into())),
+            RawWindowHandle::Wayland(handle) => {
+                let d = window
+                    .display_handle()
+                    .map_err(BlockingDialogError::Handle)?;
+
+                let RawDisplayHandle::Wayland(display) = d.as_raw() else {
+                    return Err(BlockingDialogError::Handle(HandleError::NotSupported));
+                };
+
+                let surface = handle.surface.as_ptr();
+                let display = display.display.as_ptr();
+
+                // The compositor may not support xdg-foreign, in which case
+                // the dialog is simply shown without a parent
+                let exported = unsafe { export_surface(surface, display) };
+                Ok(exported.unwrap_or_else(Self::empty))
+            }
+            _ => Err(BlockingDialogError::Handle(HandleError::NotSupported)),
+        }
+    }
+
+    pub fn empty() -> Self {
+        WindowIdentifier {
+            handle: String::new(),
+            exported: None,
+        }
+    }
+
+    fn x11(xid: c_ulong) -> Self {
+        WindowIdentifier {
+            handle: format!("x11:{xid:x}"),
+            exported: None,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.handle
+    }
+}
+
+impl Drop for WindowIdentifier {
+    fn drop(&mut self) {
+        if let Some((conn, exported)) = self.exported.take() {
+            exported.destroy();
+            let _ = conn.flush();
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    handle: Option<String>,
+    exporter: Option<ZxdgExporterV2>,
+}
+
+impl Dispatch<wl_registry::WlRegistry, ()> for State {
+    fn event(
+        state: &mut Self,
+        registry: &wl_registry::WlRegistry,
+        event: wl_registry::Event,
+        _data: &(),
+        _conn: &Connection,
+        qhandle: &QueueHandle<Self>,
+    ) {
+        if let wl_registry::Event::Global {
+            name, interface, ..
+        } = event
+            && interface == ZxdgExporterV2::interface().name
+        {
+            state.exporter = Some(registry.bind(name, 1, qhandle, ()));
+        }
+    }
+}
+
+impl Dispatch<ZxdgExporterV2, ()> for State {
+    fn event(
+        _state: &mut Self,
+        _proxy: &ZxdgExporterV2,
+        _event: <ZxdgExporterV2 as Proxy>::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qhandle: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZxdgExportedV2, ()> for State {
+    fn event(
+        state: &mut Self,
+        _proxy: &ZxdgExportedV2,
+        event: zxdg_exported_v2::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qhandle: &QueueHandle<Self>,
+    ) {
+        if let zxdg_exported_v2::Event::Handle { handle } = event {
+            state.handle = Some(handle);
+        }
+    }
+}
+
+unsafe fn export_surface(surface: *mut c_void, display: *mut c_void) -> Option<WindowIdentifier> {
+    let backend = unsafe { Backend::from_foreign_display(display.cast()) };
+    let conn = Connection::from_backend(backend);
+
+    let surface_id = unsafe { ObjectId::from_ptr(WlSurface::interface(), surface.cast()) }.ok()?;
+    let surface = WlSurface::from_id(&conn, surface_id).ok()?;
+
+    let mut event_queue = conn.new_event_queue();
+    let qhandle = event_queue.handle();
+    let mut state = State::default();
+
+    let _registry = conn.display().get_registry(&qhandle, ());
+    event_queue.roundtrip(&mut state).ok()?;
+
+    let exporter = state.exporter.take()?;
+    let exported = exporter.export_toplevel(&surface, &qhandle, ());
+    let result = event_queue.roundtrip(&mut state);
+    exporter.destroy();
+
+    match (result, state.handle) {
+        (Ok(_), Some(handle)) => Some(WindowIdentifier {
+            handle: format!("wayland:{handle}"),
+            exported: Some((conn, exported)),
+        }),
+        _ => {
+            exported.destroy();
+            None
+        }
+    }
+}
@@ -0,0 +1,918 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::date;
+use super::rgba;
+use super::subprocess::{
+    exited, parse_indices, parse_paths, parse_secret, run, run_with_code, spawn,
+};
+use crate::progress::{POLL_INTERVAL, ProgressState};
+use crate::{
+    BlockingDialogError, BlockingDialogLevel, BlockingFormDialogField, BlockingFormDialogFieldKind,
+    BlockingFormDialogValue, BlockingListDialogMode, BlockingPickFilesDialogFilter,
+    BlockingQuestionDialogResponse, Credentials, Date, Progress, Rgba, Secret,
+};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+const PROGRAM: &str = "zenity";
+/// Unit separator, which nobody types into a form
+const FORM_SEPARATOR: char = '\x1f';
+
+fn invalid_color(output: &str) -> BlockingDialogError {
+    BlockingDialogError::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{PROGRAM} printed an invalid color: {output}"),
+    ))
+}
+
+fn invalid_date(output: &str) -> BlockingDialogError {
+    BlockingDialogError::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{PROGRAM} printed an invalid date: {output}"),
+    ))
+}
+
+fn invalid_choice(label: &str) -> BlockingDialogError {
+    BlockingDialogError::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{PROGRAM} printed no valid choice for {label}"),
+    ))
+}
+
+/// Unlike colors and dates, the output isn't shown since it holds a password
+fn invalid_credentials() -> BlockingDialogError {
+    BlockingDialogError::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{PROGRAM} printed credentials without a separator"),
+    ))
+}
+
+fn get_icon_name(level: BlockingDialogLevel) -> &'static str {
+    match level {
+        BlockingDialogLevel::Info => "dialog-information",
+        BlockingDialogLevel::Warning => "dialog-warning",
+        BlockingDialogLevel::Error => "dialog-error",
+    }
+}
+
+fn push_filters(args: &mut Vec<OsString>, filter: &[BlockingPickFilesDialogFilter]) {
+    for entry in filter {
+        let patterns = entry
+            .extensions
+            .iter()
+            .map(|ext| format!("*.{ext}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        args.push(format!("--file-filter={} | {}", entry.name, patterns).into());
+    }
+}
+
+/// `--filename` opens in the directory when the path ends with a slash
+fn push_filename(
+    args: &mut Vec<OsString>,
+    starting_directory: Option<&Path>,
+    default_filename: Option<&str>,
+) {
+    let path = match (starting_directory, default_filename) {
+        (Some(dir), name) => dir.join(name.unwrap_or_default()),
+        (None, Some(name)) => PathBuf::from(name),
+        (None, None) => return,
+    };
+
+    let mut arg = OsString::from("--filename=");
+    arg.push(path);
+    args.push(arg);
+}
+
+pub fn alert(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+) -> Result<(), BlockingDialogError> {
+    let kind = match level {
+        BlockingDialogLevel::Info => "--info",
+        BlockingDialogLevel::Warning => "--warning",
+        BlockingDialogLevel::Error => "--error",
+    };
+
+    let args = [kind, "--no-markup", "--title", title, "--text", message];
+    let _ = run(PROGRAM, args)?;
+
+    Ok(())
+}
+
+pub fn confirm(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+    accept_label: Option<&str>,
+    cancel_label: Option<&str>,
+) -> Result<bool, BlockingDialogError> {
+    let icon = format!("--icon-name={}", get_icon_name(level));
+    let mut args = vec![
+        "--question".to_string(),
+        "--no-markup".to_string(),
+        icon,
+        "--title".to_string(),
+        title.to_string(),
+        "--text".to_string(),
+        message.to_string(),
+    ];
+
+    if let Some(accept_label) = accept_label {
+        args.push(format!("--ok-label={accept_label}"));
+    }
+
+    if let Some(cancel_label) = cancel_label {
+        args.push(format!("--cancel-label={cancel_label}"));
+    }
+
+    let output = run(PROGRAM, args)?;
+
+    Ok(output.is_some())
+}
+
+pub fn question(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+    let buttons = ["Yes", "No", "Cancel"];
+
+    let response = match self::message(title, message, level, &buttons, 2)? {
+        0 => BlockingQuestionDialogResponse::Yes,
+        1 => BlockingQuestionDialogResponse::No,
+        _ => BlockingQuestionDialogResponse::Cancel,
+    };
+
+    Ok(response)
+}
+
+pub fn message(
+    title: &str,
+    message: &str,
+    level: BlockingDialogLevel,
+    buttons: &[&str],
+    cancel_button: usize,
+) -> Result<usize, BlockingDialogError> {
+    let mut args = vec![
+        "--question".to_string(),
+        "--switch".to_string(),
+        "--no-markup".to_string(),
+        format!("--icon-name={}", get_icon_name(level)),
+        "--title".to_string(),
+        title.to_string(),
+        "--text".to_string(),
+        message.to_string(),
+    ];
+
+    // With --switch only the extra buttons are shown, each printing its
+    // label, so closing the window can be told apart from any of them
+    for button in buttons {
+        args.push(format!("--extra-button={button}"));
+    }
+
+    match run_with_code(PROGRAM, args)? {
+        (0 | 1, stdout) => Ok(buttons
+            .iter()
+            .position(|button| button.as_bytes() == stdout)
+            .unwrap_or(cancel_button)),
+        (code, _) => Err(exited(PROGRAM, code)),
+    }
+}
+
+pub fn input(
+    title: &str,
+    message: &str,
+    default_text: Option<&str>,
+) -> Result<Option<String>, BlockingDialogError> {
+    let mut args = vec!["--entry", "--title", title, "--text", message];
+
+    if let Some(default_text) = default_text {
+        args.extend(["--entry-text", default_text]);
+    }
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(|stdout| String::from_utf8_lossy(&stdout).into_owned()))
+}
+
+/// zenity's password dialog has a fixed prompt, so only the title is shown
+pub fn password(title: &str) -> Result<Option<Secret>, BlockingDialogError> {
+    let args = ["--password", "--title", title];
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_secret))
+}
+
+/// Shown as a form, which can't prefill the username
+pub fn login(title: &str, message: &str) -> Result<Option<Credentials>, BlockingDialogError> {
+    let args = [
+        "--forms".to_string(),
+        "--title".to_string(),
+        title.to_string(),
+        "--text".to_string(),
+        message.to_string(),
+        format!("--separator={FORM_SEPARATOR}"),
+        "--add-entry=Username".to_string(),
+        "--add-password=Password".to_string(),
+    ];
+
+    let Some(output) = run(PROGRAM, args)? else {
+        return Ok(None);
+    };
+
+    let output = parse_secret(output);
+    let Some((username, password)) = output.expose().split_once(FORM_SEPARATOR) else {
+        return Err(invalid_credentials());
+    };
+
+    Ok(Some(Credentials {
+        username: username.to_string(),
+        password: Secret::new(password.to_string()),
+    }))
+}
+
+/// Combo values are split on | with no way to escape it
+pub fn can_show_form(fields: &[BlockingFormDialogField]) -> bool {
+    fields.iter().all(|field| match field.kind {
+        BlockingFormDialogFieldKind::Dropdown(options) => {
+            options.iter().all(|option| !option.contains('|'))
+        }
+        _ => true,
+    })
+}
+
+pub fn form(
+    title: &str,
+    message: &str,
+    fields: &[BlockingFormDialogField],
+) -> Result<Option<HashMap<String, BlockingFormDialogValue>>, BlockingDialogError> {
+    // Values are printed on one line, and may contain newlines and | themselves
+    let mut args = vec![
+        "--forms".to_string(),
+        "--title".to_string(),
+        title.to_string(),
+        "--text".to_string(),
+        message.to_string(),
+        format!("--separator={FORM_SEPARATOR}"),
+        "--forms-date-format=%Y-%m-%d".to_string(),
+    ];
+
+    for field in fields {
+        let label = field.label;
+
+        match field.kind {
+            BlockingFormDialogFieldKind::Text => args.push(format!("--add-entry={label}")),
+            BlockingFormDialogFieldKind::Password => args.push(format!("--add-password={label}")),
+            BlockingFormDialogFieldKind::Multiline => {
+                args.push(format!("--add-multiline-entry={label}"))
+            }
+            BlockingFormDialogFieldKind::Dropdown(options) => {
+                args.push(format!("--add-combo={label}"));
+                args.push(format!("--combo-values={}", options.join("|")));
+            }
+            // Forms have no checkbox, a Yes/No combo is the closest
+            BlockingFormDialogFieldKind::Checkbox => {
+                args.push(format!("--add-combo={label}"));
+                args.push("--combo-values=Yes|No".to_string());
+            }
+            BlockingFormDialogFieldKind::Date => args.push(format!("--add-calendar={label}")),
+        }
+    }
+
+    let Some(output) = run(PROGRAM, args)? else {
+        return Ok(None);
+    };
+
+    // Passwords are part of the output, so it's wiped like one
+    let output = parse_secret(output);
+    let mut values = output.expose().split(FORM_SEPARATOR);
+
+    let values = fields
+        .iter()
+        .map(|field| {
+            let value = values.next().unwrap_or_default();
+
+            let value = match field.kind {
+                BlockingFormDialogFieldKind::Text | BlockingFormDialogFieldKind::Multiline => {
+                    BlockingFormDialogValue::Text(value.to_string())
+                }
+                BlockingFormDialogFieldKind::Password => {
+                    BlockingFormDialogValue::Password(Secret::new(value.to_string()))
+                }
+                // The combo starts empty, and may be left so
+                BlockingFormDialogFieldKind::Dropdown(options) => {
+                    let index = options.iter().position(|o| *o == value);
+                    BlockingFormDialogValue::Dropdown(
+                        index.ok_or_else(|| invalid_choice(field.label))?,
+                    )
+                }
+                BlockingFormDialogFieldKind::Checkbox => {
+                    BlockingFormDialogValue::Checkbox(value == "Yes")
+                }
+                BlockingFormDialogFieldKind::Date => {
+                    let (year, month, day) =
+                        date::parse(value).ok_or_else(|| invalid_date(value))?;
+                    BlockingFormDialogValue::Date(year, month, day)
+                }
+            };
+
+            Ok((field.id.to_string(), value))
+        })
+        .collect::<Result<_, BlockingDialogError>>()?;
+
+    Ok(Some(values))
+}
+
+pub fn list(
+    title: &str,
+    message: &str,
+    columns: &[&str],
+    rows: &[&[&str]],
+    selected: &[usize],
+    mode: BlockingListDialogMode,
+) -> Result<Vec<usize>, BlockingDialogError> {
+    let mut args = vec![
+        "--list".to_string(),
+        "--title".to_string(),
+        title.to_string(),
+        "--text".to_string(),
+        message.to_string(),
+    ];
+
+    // Rows are told apart by a hidden index column, after the check column if any
+    let index_column = match mode {
+        BlockingListDialogMode::Radio => {
+            args.extend(["--radiolist".to_string(), "--column=".to_string()]);
+            2
+        }
+        BlockingListDialogMode::Checklist => {
+            args.extend(["--checklist".to_string(), "--column=".to_string()]);
+            args.push("--separator=\n".to_string());
+            2
+        }
+        BlockingListDialogMode::Plain => 1,
+    };
+
+    args.push("--column=#".to_string());
+    args.push(format!("--hide-column={index_column}"));
+    args.push(format!("--print-column={index_column}"));
+
+    let width = rows
+        .iter()
+        .map(|row| row.len())
+        .chain([columns.len(), 1])
+        .max()
+        .unwrap_or(1);
+
+    for i in 0..width {
+        args.push(format!(
+            "--column={}",
+            columns.get(i).copied().unwrap_or_default()
+        ));
+    }
+
+    for (i, row) in rows.iter().enumerate() {
+        if mode != BlockingListDialogMode::Plain {
+            let checked = if selected.contains(&i) {
+                "TRUE"
+            } else {
+                "FALSE"
+            };
+            args.push(checked.to_string());
+        }
+
+        args.push(i.to_string());
+
+        for column in 0..width {
+            args.push(row.get(column).copied().unwrap_or_default().to_string());
+        }
+    }
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_indices).unwrap_or_default())
+}
+
+pub fn color(title: &str, initial: Option<Rgba>) -> Result<Option<Rgba>, BlockingDialogError> {
+    let mut args = vec![
+        "--color-selection".to_string(),
+        "--title".to_string(),
+        title.to_string(),
+    ];
+
+    if let Some(initial) = initial {
+        args.push(format!("--color={}", rgba::to_css(initial)));
+    }
+
+    let Some(output) = run(PROGRAM, args)? else {
+        return Ok(None);
+    };
+
+    let output = String::from_utf8_lossy(&output);
+    rgba::parse(&output)
+        .map(Some)
+        .ok_or_else(|| invalid_color(&output))
+}
+
+pub fn date(
+    title: &str,
+    message: &str,
+    initial: Date,
+) -> Result<Option<Date>, BlockingDialogError> {
+    // The default format follows the locale, so it's pinned to one that can be parsed
+    let args = [
+        "--calendar".to_string(),
+        "--title".to_string(),
+        title.to_string(),
+        "--text".to_string(),
+        message.to_string(),
+        format!("--year={}", initial.year),
+        format!("--month={}", initial.month),
+        format!("--day={}", initial.day),
+        "--date-format=%Y-%m-%d".to_string(),
+    ];
+
+    let Some(output) = run(PROGRAM, args)? else {
+        return Ok(None);
+    };
+
+    let output = String::from_utf8_lossy(&output);
+    date::parse(&output)
+        .map(|(year, month, day)| Some(Date::new(year, month, day)))
+        .ok_or_else(|| invalid_date(&output))
+}
+
+/// The lines zenity reads to update its progress dialog
+fn get_progress_lines(state: &ProgressState) -> String {
+    let mut lines = match state.fraction {
+        Some(fraction) => format!("pulsate:false\n{}\n", (fraction * 100.0).round()),
+        None => "pulsate:true\n".to_string(),
+    };
+
+    if let Some(text) = &state.text {
+        lines.push_str(&format!("# {}\n", text.replace('\n', " ")));
+    }
+
+    lines
+}
+
+pub fn progress(
+    title: &str,
+    message: &str,
+    progress: &Progress,
+) -> Result<(), BlockingDialogError> {
+    let args = [
+        "--progress",
+        "--auto-close",
+        "--title",
+        title,
+        "--text",
+        message,
+    ];
+
+    let mut child = spawn(PROGRAM, args)?;
+    let mut stdin = child.stdin.take();
+    let mut shown = None;
+
+    while !progress.is_finished() {
+        if let Some(status) = child.try_wait()? {
+            // Auto-closed at 100%, or cancelled
+            return match status.code() {
+                Some(0) => Ok(()),
+                Some(1) => {
+                    progress.cancel();
+                    Ok(())
+                }
+                _ => Err(exited(PROGRAM, status)),
+            };
+        }
+
+        let state = progress.get_state();
+
+        if shown.as_ref() != Some(&state) {
+            // Writing fails once zenity exited, which try_wait sees next time
+            if let Some(stdin) = &mut stdin {
+                let _ = stdin.write_all(get_progress_lines(&state).as_bytes());
+            }
+
+            shown = Some(state);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    // The end of input closes the dialog because of --auto-close
+    drop(stdin);
+    child.wait()?;
+
+    Ok(())
+}
+
+pub fn pick_files(
+    title: &str,
+    multiple: bool,
+    filter: &[BlockingPickFilesDialogFilter],
+    starting_directory: Option<&Path>,
+) -> Result<Vec<PathBuf>, BlockingDialogError> {
+    let mut args: Vec<OsString> = vec!["--file-selection".into(), "--title".into(), title.into()];
+
+    if multiple {
+        args.push("--multiple".into());
+        args.push("--separator=\n".into());
+    }
+
+    push_filename(&mut args, starting_directory, None);
+
+    push_filters(&mut args, filter);
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_paths).unwrap_or_default())
+}
+
+pub fn save_file(
+    title: &str,
+    default_filename: Option<&str>,
+    filter: &[BlockingPickFilesDialogFilter],
+    starting_directory: Option<&Path>,
+) -> Result<Option<PathBuf>, BlockingDialogError> {
+    let mut args: Vec<OsString> = vec![
+        "--file-selection".into(),
+        "--save".into(),
+        "--confirm-overwrite".into(),
+        "--title".into(),
+        title.into(),
+    ];
+
+    push_filename(&mut args, starting_directory, default_filename);
+
+    push_filters(&mut args, filter);
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_paths).and_then(|mut paths| paths.pop()))
+}
+
+pub fn pick_directory(
+    title: &str,
+    starting_directory: Option<&Path>,
+) -> Result<Option<PathBuf>, BlockingDialogError> {
+    let mut args: Vec<OsString> = vec![
+        "--file-selection".into(),
+        "--directory".into(),
+        "--title".into(),
+        title.into(),
+    ];
+
+    push_filename(&mut args, starting_directory, None);
+
+    let output = run(PROGRAM, args)?;
+    Ok(output.map(parse_paths).and_then(|mut paths| paths.pop()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::os_dialog::subprocess::tests::Stub;
+
+    const FILTER: &[BlockingPickFilesDialogFilter] = &[BlockingPickFilesDialogFilter {
+        name: "Images",
+        extensions: &["png", "jpg"],
+    }];
+
+    #[test]
+    fn alerts() {
+        let stub = Stub::new(PROGRAM, 0, "");
+        alert("Title", "Text", BlockingDialogLevel::Warning).unwrap();
+        assert_eq!(
+            stub.args(),
+            [
+                "--warning",
+                "--no-markup",
+                "--title",
+                "Title",
+                "--text",
+                "Text"
+            ]
+        );
+
+        let _stub = Stub::new(PROGRAM, 5, "");
+        assert!(alert("Title", "Text", BlockingDialogLevel::Info).is_err());
+    }
+
+    #[test]
+    fn confirms() {
+        let stub = Stub::new(PROGRAM, 0, "");
+        let level = BlockingDialogLevel::Error;
+        assert!(confirm("Title", "Text", level, Some("Delete"), Some("Keep")).unwrap());
+        assert_eq!(
+            stub.args(),
+            [
+                "--question",
+                "--no-markup",
+                "--icon-name=dialog-error",
+                "--title",
+                "Title",
+                "--text",
+                "Text",
+                "--ok-label=Delete",
+                "--cancel-label=Keep",
+            ]
+        );
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert!(!confirm("Title", "Text", level, None, None).unwrap());
+
+        let _stub = Stub::new(PROGRAM, 5, "");
+        assert!(confirm("Title", "Text", level, None, None).is_err());
+    }
+
+    #[test]
+    fn asks_questions() {
+        let stub = Stub::new(PROGRAM, 1, "No");
+        let response = question("Title", "Text", BlockingDialogLevel::Info).unwrap();
+        assert_eq!(response, BlockingQuestionDialogResponse::No);
+        assert_eq!(
+            stub.args()[8..],
+            [
+                "--extra-button=Yes",
+                "--extra-button=No",
+                "--extra-button=Cancel"
+            ]
+        );
+
+        // Closing the window prints nothing
+        let _stub = Stub::new(PROGRAM, 1, "");
+        let response = question("Title", "Text", BlockingDialogLevel::Info).unwrap();
+        assert_eq!(response, BlockingQuestionDialogResponse::Cancel);
+    }
+
+    #[test]
+    fn shows_messages() {
+        let buttons = ["Save", "Discard", "Cancel"];
+        let level = BlockingDialogLevel::Info;
+
+        let _stub = Stub::new(PROGRAM, 1, "Discard");
+        assert_eq!(message("Title", "Text", level, &buttons, 2).unwrap(), 1);
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert_eq!(message("Title", "Text", level, &buttons, 2).unwrap(), 2);
+
+        let _stub = Stub::new(PROGRAM, 5, "");
+        assert!(message("Title", "Text", level, &buttons, 2).is_err());
+    }
+
+    #[test]
+    fn asks_for_input() {
+        let stub = Stub::new(PROGRAM, 0, "typed text\n");
+        let text = input("Title", "Name:", Some("default")).unwrap();
+        assert_eq!(text.as_deref(), Some("typed text"));
+        assert_eq!(
+            stub.args(),
+            [
+                "--entry",
+                "--title",
+                "Title",
+                "--text",
+                "Name:",
+                "--entry-text",
+                "default"
+            ]
+        );
+
+        let stub = Stub::new(PROGRAM, 0, "s3cr|t\n");
+        let secret = password("Title").unwrap().unwrap();
+        assert_eq!(secret.expose(), "s3cr|t");
+        assert_eq!(stub.args(), ["--password", "--title", "Title"]);
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert!(input("Title", "Name:", None).unwrap().is_none());
+        assert!(password("Title").unwrap().is_none());
+    }
+
+    #[test]
+    fn logs_in() {
+        let stub = Stub::new(PROGRAM, 0, "al|ice\x1fpa|ss\n");
+        let credentials = login("Title", "Text").unwrap().unwrap();
+        assert_eq!(credentials.username, "al|ice");
+        assert_eq!(credentials.password.expose(), "pa|ss");
+        assert_eq!(
+            stub.args(),
+            [
+                "--forms",
+                "--title",
+                "Title",
+                "--text",
+                "Text",
+                "--separator=\x1f",
+                "--add-entry=Username",
+                "--add-password=Password"
+            ]
+        );
+
+        let _stub = Stub::new(PROGRAM, 0, "s3cret\n");
+        let err = login("Title", "Text").unwrap_err();
+        assert!(
+            matches!(&err, BlockingDialogError::Io(err) if err.kind() == io::ErrorKind::InvalidData)
+        );
+        assert!(!err.to_string().contains("s3cret"));
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert!(login("Title", "Text").unwrap().is_none());
+    }
+
+    #[test]
+    fn fills_in_forms() {
+        let fields = [
+            BlockingFormDialogField {
+                id: "name",
+                label: "Name",
+                kind: BlockingFormDialogFieldKind::Text,
+            },
+            BlockingFormDialogField {
+                id: "size",
+                label: "Size",
+                kind: BlockingFormDialogFieldKind::Dropdown(&["S", "M", "L"]),
+            },
+            BlockingFormDialogField {
+                id: "gift",
+                label: "Gift",
+                kind: BlockingFormDialogFieldKind::Checkbox,
+            },
+            BlockingFormDialogField {
+                id: "when",
+                label: "When",
+                kind: BlockingFormDialogFieldKind::Date,
+            },
+        ];
+
+        let stub = Stub::new(PROGRAM, 0, "a|b\x1fM\x1fYes\x1f2026-02-03\n");
+        let values = form("Title", "Text", &fields).unwrap().unwrap();
+        assert_eq!(
+            stub.args()[6..],
+            [
+                "--forms-date-format=%Y-%m-%d",
+                "--add-entry=Name",
+                "--add-combo=Size",
+                "--combo-values=S|M|L",
+                "--add-combo=Gift",
+                "--combo-values=Yes|No",
+                "--add-calendar=When",
+            ]
+        );
+
+        assert!(matches!(&values["name"], BlockingFormDialogValue::Text(t) if t == "a|b"));
+        assert!(matches!(
+            values["size"],
+            BlockingFormDialogValue::Dropdown(1)
+        ));
+        assert!(matches!(
+            values["gift"],
+            BlockingFormDialogValue::Checkbox(true)
+        ));
+        assert!(matches!(
+            values["when"],
+            BlockingFormDialogValue::Date(2026, 2, 3)
+        ));
+
+        for output in ["a\x1f\x1fYes\x1f2026-02-03\n", "a\x1fM\x1fYes\x1fsoon\n"] {
+            let _stub = Stub::new(PROGRAM, 0, output);
+            let err = form("Title", "Text", &fields).unwrap_err();
+            assert!(
+                matches!(err, BlockingDialogError::Io(err) if err.kind() == io::ErrorKind::InvalidData)
+            );
+        }
+
+        assert!(can_show_form(&fields));
+        let fields = [BlockingFormDialogField {
+            id: "pipe",
+            label: "Pipe",
+            kind: BlockingFormDialogFieldKind::Dropdown(&["a|b"]),
+        }];
+        assert!(!can_show_form(&fields));
+    }
+
+    #[test]
+    fn lists_rows() {
+        let rows: &[&[&str]] = &[&["a", "1"], &["b"]];
+
+        let stub = Stub::new(PROGRAM, 0, "1\n0\n");
+        let mode = BlockingListDialogMode::Checklist;
+        let picked = list("Title", "Text", &["Name", "Size"], rows, &[1], mode).unwrap();
+        assert_eq!(picked, [0, 1]);
+        assert_eq!(
+            stub.args()[5..],
+            [
+                "--checklist",
+                "--column=",
+                "--separator=\n",
+                "--column=#",
+                "--hide-column=2",
+                "--print-column=2",
+                "--column=Name",
+                "--column=Size",
+                "FALSE",
+                "0",
+                "a",
+                "1",
+                "TRUE",
+                "1",
+                "b",
+                "",
+            ]
+        );
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert!(
+            list("Title", "Text", &[], rows, &[], mode)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn picks_colors_and_dates() {
+        let stub = Stub::new(PROGRAM, 0, "rgb(255,128,0)\n");
+        let color = color("Title", Some(Rgba::rgb(1, 2, 3))).unwrap();
+        assert_eq!(color, Some(Rgba::rgb(255, 128, 0)));
+        assert_eq!(
+            stub.args(),
+            [
+                "--color-selection",
+                "--title",
+                "Title",
+                "--color=rgba(1,2,3,1)"
+            ]
+        );
+
+        let stub = Stub::new(PROGRAM, 0, "2026-12-31\n");
+        let picked = date("Title", "Text", Date::new(2026, 1, 9)).unwrap();
+        assert_eq!(picked, Some(Date::new(2026, 12, 31)));
+        assert_eq!(
+            stub.args()[5..],
+            [
+                "--year=2026",
+                "--month=1",
+                "--day=9",
+                "--date-format=%Y-%m-%d"
+            ]
+        );
+
+        let _stub = Stub::new(PROGRAM, 0, "31/12/2026\n");
+        assert!(date("Title", "Text", Date::new(2026, 1, 9)).is_err());
+    }
+
+    #[test]
+    fn picks_files() {
+        let stub = Stub::new(PROGRAM, 0, "/tmp/a b.png\n/tmp/c|d.jpg\n");
+        let paths = pick_files("Title", true, FILTER, Some(Path::new("/tmp"))).unwrap();
+        assert_eq!(
+            paths,
+            [PathBuf::from("/tmp/a b.png"), PathBuf::from("/tmp/c|d.jpg")]
+        );
+        assert_eq!(
+            stub.args(),
+            [
+                "--file-selection",
+                "--title",
+                "Title",
+                "--multiple",
+                "--separator=\n",
+                "--filename=/tmp/",
+                "--file-filter=Images | *.png *.jpg",
+            ]
+        );
+
+        let _stub = Stub::new(PROGRAM, 1, "");
+        assert!(pick_files("Title", false, &[], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn saves_files_and_picks_directories() {
+        let stub = Stub::new(PROGRAM, 0, "/tmp/out.png\n");
+        let path = save_file("Title", Some("out.png"), &[], Some(Path::new("/tmp"))).unwrap();
+        assert_eq!(path, Some(PathBuf::from("/tmp/out.png")));
+        assert_eq!(
+            stub.args(),
+            [
+                "--file-selection",
+                "--save",
+                "--confirm-overwrite",
+                "--title",
+                "Title",
+                "--filename=/tmp/out.png",
+            ]
+        );
+
+        let stub = Stub::new(PROGRAM, 0, "/home\n");
+        let path = pick_directory("Title", None).unwrap();
+        assert_eq!(path, Some(PathBuf::from("/home")));
+        assert_eq!(
+            stub.args(),
+            ["--file-selection", "--directory", "--title", "Title"]
+        );
+    }
+}
@@ -1,42 +1,15 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use crate::{BlockingAlertDialog, BlockingDialogError, BlockingDialogLevel};
-use block2::RcBlock;
-use objc2::{MainThreadMarker, rc::Retained};
-use objc2_app_kit::{NSAlert, NSAlertStyle, NSApplication, NSImage, NSView};
+use super::{get_ns_alert_icon, get_ns_alert_style, get_ns_window, run_modal};
+use crate::{BlockingAlertDialog, BlockingDialogError};
+use objc2::MainThreadMarker;
+use objc2_app_kit::NSAlert;
 use objc2_foundation::NSString;
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
-
-fn get_ns_alert_style(level: BlockingDialogLevel) -> NSAlertStyle {
-    match level {
-        BlockingDialogLevel::Info => NSAlertStyle::Informational,
-        BlockingDialogLevel::Warning => NSAlertStyle::Warning,
-        BlockingDialogLevel::Error => NSAlertStyle::Warning,
-    }
-}
-
-fn get_ns_alert_icon(level: BlockingDialogLevel) -> Option<Retained<NSImage>> {
-    match level {
-        BlockingDialogLevel::Info => NSImage::imageWithSystemSymbolName_accessibilityDescription(
-            &NSString::from_str("info.circle"),
-            Some(&NSString::from_str("Info")),
-        ),
-        BlockingDialogLevel::Warning => {
-            NSImage::imageWithSystemSymbolName_accessibilityDescription(
-                &NSString::from_str("exclamationmark.triangle"),
-                Some(&NSString::from_str("Warning")),
-            )
-        }
-        BlockingDialogLevel::Error => NSImage::imageWithSystemSymbolName_accessibilityDescription(
-            &NSString::from_str("multiply.circle"),
-            Some(&NSString::from_str("Error")),
-        ),
-    }
-}
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingAlertDialog<'a, W> {
-    pub fn show(&self) -> Result<(), BlockingDialogError> {
+    pub(crate) fn show_native(&self) -> Result<(), BlockingDialogError> {
         let Some(mtm) = MainThreadMarker::new() else {
             return Err(BlockingDialogError::NotOnMainThread);
         };
@@ -53,26 +26,9 @@ impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingAlertDialog<'a, W> {
             unsafe { ns_alert.setIcon(Some(icon.as_ref())) }
         }
 
-        let w = match self.window.window_handle() {
-            Ok(w) => w,
-            Err(err) => return Err(BlockingDialogError::Handle(err)),
-        };
-
-        let RawWindowHandle::AppKit(w) = w.as_raw() else {
-            return Err(BlockingDialogError::Handle(HandleError::NotSupported));
-        };
-
-        let ns_view = unsafe { w.ns_view.cast::<NSView>().as_ref() };
-        let Some(ns_window) = ns_view.window() else {
-            return Err(BlockingDialogError::Handle(HandleError::Unavailable));
-        };
-
-        let handler = RcBlock::new(move |_| {
-            NSApplication::sharedApplication(mtm).stopModal();
-        });
+        let ns_window = get_ns_window(&self.window)?;
 
-        ns_alert.beginSheetModalForWindow_completionHandler(&ns_window, Some(&handler));
-        let _ = NSApplication::sharedApplication(mtm).runModalForWindow(&ns_window);
+        run_modal(&*ns_alert, ns_window.as_deref(), mtm);
 
         Ok(())
     }
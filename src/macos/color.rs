@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_ns_window, run_modal};
+use crate::{BlockingColorDialog, BlockingDialogError, Rgba};
+use objc2::{MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{
+    NSAlert, NSAlertFirstButtonReturn, NSColor, NSColorPanel, NSColorSpace, NSColorWell,
+};
+use objc2_foundation::{NSPoint, NSRect, NSSize, NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+const WELL_WIDTH: f64 = 64.0;
+const WELL_HEIGHT: f64 = 32.0;
+
+fn get_channel(component: f64) -> u8 {
+    (component.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingColorDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Rgba>, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        // The well opens the shared color panel, which works while the alert is modal
+        let rect = NSRect::new(NSPoint::ZERO, NSSize::new(WELL_WIDTH, WELL_HEIGHT));
+        let well = NSColorWell::initWithFrame(NSColorWell::alloc(mtm), rect);
+
+        if let Some(initial) = self.initial {
+            let channel = |c: u8| c as f64 / 255.0;
+            let color = NSColor::colorWithSRGBRed_green_blue_alpha(
+                channel(initial.r),
+                channel(initial.g),
+                channel(initial.b),
+                channel(initial.a),
+            );
+            well.setColor(&color);
+        }
+
+        NSColorPanel::sharedColorPanel(mtm).setShowsAlpha(self.alpha);
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setAccessoryView(Some(&well));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("OK"));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        ns_alert.layout();
+        well.activate(true);
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        // Leave the shared panel closed and no longer tied to this well
+        well.deactivate();
+        NSColorPanel::sharedColorPanel(mtm).orderOut(None);
+
+        if resp != NSAlertFirstButtonReturn {
+            return Ok(None);
+        }
+
+        // The panel may have picked the color in any color space
+        let Some(color) = well
+            .color()
+            .colorUsingColorSpace(&NSColorSpace::sRGBColorSpace())
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(Rgba::new(
+            get_channel(color.redComponent()),
+            get_channel(color.greenComponent()),
+            get_channel(color.blueComponent()),
+            get_channel(color.alphaComponent()),
+        )))
+    }
+}
@@ -1,44 +1,15 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use crate::{BlockingConfirmDialog, BlockingDialogError, BlockingDialogLevel};
-use block2::RcBlock;
-use objc2::{MainThreadMarker, rc::Retained};
-use objc2_app_kit::{
-    NSAlert, NSAlertFirstButtonReturn, NSAlertStyle, NSApplication, NSImage, NSView,
-};
-use objc2_foundation::{NSString, ns_string};
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
-
-fn get_ns_alert_style(level: BlockingDialogLevel) -> NSAlertStyle {
-    match level {
-        BlockingDialogLevel::Info => NSAlertStyle::Informational,
-        BlockingDialogLevel::Warning => NSAlertStyle::Warning,
-        BlockingDialogLevel::Error => NSAlertStyle::Warning,
-    }
-}
-
-fn get_ns_alert_icon(level: BlockingDialogLevel) -> Option<Retained<NSImage>> {
-    match level {
-        BlockingDialogLevel::Info => NSImage::imageWithSystemSymbolName_accessibilityDescription(
-            &NSString::from_str("info.circle"),
-            Some(&NSString::from_str("Info")),
-        ),
-        BlockingDialogLevel::Warning => {
-            NSImage::imageWithSystemSymbolName_accessibilityDescription(
-                &NSString::from_str("exclamationmark.triangle"),
-                Some(&NSString::from_str("Warning")),
-            )
-        }
-        BlockingDialogLevel::Error => NSImage::imageWithSystemSymbolName_accessibilityDescription(
-            &NSString::from_str("multiply.circle"),
-            Some(&NSString::from_str("Error")),
-        ),
-    }
-}
+use super::{get_ns_alert_icon, get_ns_alert_style, get_ns_window, run_modal};
+use crate::{BlockingConfirmDialog, BlockingDialogError};
+use objc2::MainThreadMarker;
+use objc2_app_kit::{NSAlert, NSAlertFirstButtonReturn};
+use objc2_foundation::NSString;
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingConfirmDialog<'a, W> {
-    pub fn show(&self) -> Result<bool, BlockingDialogError> {
+    pub(crate) fn show_native(&self) -> Result<bool, BlockingDialogError> {
         let Some(mtm) = MainThreadMarker::new() else {
             return Err(BlockingDialogError::NotOnMainThread);
         };
@@ -50,33 +21,18 @@ impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingConfirmDialog<'a, W> {
         ns_alert.setMessageText(&NSString::from_str(self.title));
         ns_alert.setInformativeText(&NSString::from_str(self.message));
         ns_alert.setAlertStyle(style);
-        let _ = ns_alert.addButtonWithTitle(ns_string!("OK"));
-        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+        let accept_label = NSString::from_str(self.accept_label.unwrap_or("OK"));
+        let cancel_label = NSString::from_str(self.cancel_label.unwrap_or("Cancel"));
+        let _ = ns_alert.addButtonWithTitle(&accept_label);
+        let _ = ns_alert.addButtonWithTitle(&cancel_label);
 
         if let Some(icon) = icon {
             unsafe { ns_alert.setIcon(Some(icon.as_ref())) }
         }
 
-        let w = match self.window.window_handle() {
-            Ok(w) => w,
-            Err(err) => return Err(BlockingDialogError::Handle(err)),
-        };
-
-        let RawWindowHandle::AppKit(w) = w.as_raw() else {
-            return Err(BlockingDialogError::Handle(HandleError::NotSupported));
-        };
-
-        let handler = RcBlock::new(move |resp| {
-            NSApplication::sharedApplication(mtm).stopModalWithCode(resp);
-        });
-
-        let ns_view = unsafe { w.ns_view.cast::<NSView>().as_ref() };
-        let Some(ns_window) = ns_view.window() else {
-            return Err(BlockingDialogError::Handle(HandleError::Unavailable));
-        };
+        let ns_window = get_ns_window(&self.window)?;
 
-        ns_alert.beginSheetModalForWindow_completionHandler(&ns_window, Some(&handler));
-        let resp = NSApplication::sharedApplication(mtm).runModalForWindow(&ns_window);
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
 
         Ok(resp == NSAlertFirstButtonReturn)
     }
@@ -0,0 +1,84 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_ns_window, run_modal};
+use crate::{BlockingDateDialog, BlockingDialogError, Date};
+use objc2::rc::Retained;
+use objc2::{MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{
+    NSAlert, NSAlertFirstButtonReturn, NSDatePicker, NSDatePickerElementFlags, NSDatePickerStyle,
+};
+use objc2_foundation::{
+    NSCalendar, NSCalendarIdentifierGregorian, NSCalendarUnit, NSDate, NSString, ns_string,
+};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+/// `Date` is always Gregorian, whatever calendar the user has picked
+pub fn get_calendar() -> Option<Retained<NSCalendar>> {
+    NSCalendar::calendarWithIdentifier(unsafe { NSCalendarIdentifierGregorian })
+}
+
+/// Noon, so that no time zone change moves it to another day
+fn get_ns_date(calendar: &NSCalendar, date: Date) -> Option<Retained<NSDate>> {
+    calendar.dateWithEra_year_month_day_hour_minute_second_nanosecond(
+        1,
+        date.year as isize,
+        date.month as isize,
+        date.day as isize,
+        12,
+        0,
+        0,
+        0,
+    )
+}
+
+pub fn get_date(calendar: &NSCalendar, date: &NSDate) -> Date {
+    let year = calendar.component_fromDate(NSCalendarUnit::Year, date);
+    let month = calendar.component_fromDate(NSCalendarUnit::Month, date);
+    let day = calendar.component_fromDate(NSCalendarUnit::Day, date);
+
+    Date::new(year as i32, month as u32, day as u32)
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingDateDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Date>, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        let calendar = get_calendar().unwrap_or_else(NSCalendar::currentCalendar);
+        let get_ns_date = |date: Option<Date>| date.and_then(|date| get_ns_date(&calendar, date));
+
+        let date_picker = NSDatePicker::new(mtm);
+        date_picker.setDatePickerStyle(NSDatePickerStyle::ClockAndCalendar);
+        date_picker.setDatePickerElements(NSDatePickerElementFlags::YearMonthDay);
+        date_picker.setCalendar(Some(&calendar));
+        date_picker.setMinDate(get_ns_date(self.min).as_deref());
+        date_picker.setMaxDate(get_ns_date(self.max).as_deref());
+
+        let today = get_date(&calendar, &NSDate::now());
+        let initial = self.clamp(self.initial.unwrap_or(today));
+        if let Some(initial) = get_ns_date(Some(initial)) {
+            date_picker.setDateValue(&initial);
+        }
+        date_picker.sizeToFit();
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAccessoryView(Some(&date_picker));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("OK"));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        if resp != NSAlertFirstButtonReturn {
+            return Ok(None);
+        }
+
+        // The picker already kept the date within the bounds
+        Ok(Some(get_date(&calendar, &date_picker.dateValue())))
+    }
+}
@@ -0,0 +1,216 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::date::{get_calendar, get_date};
+use super::{get_ns_window, run_modal};
+use crate::{
+    BlockingDialogError, BlockingFormDialog, BlockingFormDialogFieldKind, BlockingFormDialogValue,
+    Secret,
+};
+use objc2::rc::Retained;
+use objc2::{MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{
+    NSAlert, NSAlertFirstButtonReturn, NSButton, NSControlStateValueOn, NSDatePicker,
+    NSDatePickerElementFlags, NSDatePickerStyle, NSPopUpButton, NSScrollView, NSSecureTextField,
+    NSTextField, NSTextView, NSView,
+};
+use objc2_foundation::{NSArray, NSCalendar, NSDate, NSPoint, NSRect, NSSize, NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::collections::HashMap;
+
+const FORM_WIDTH: f64 = 300.0;
+const LABEL_HEIGHT: f64 = 17.0;
+const CONTROL_HEIGHT: f64 = 24.0;
+const MULTILINE_HEIGHT: f64 = 72.0;
+const FIELD_GAP: f64 = 8.0;
+
+enum Control {
+    Text(Retained<NSTextField>),
+    Password(Retained<NSSecureTextField>),
+    Multiline(Retained<NSTextView>),
+    Dropdown(Retained<NSPopUpButton>),
+    Checkbox(Retained<NSButton>),
+    Date(Retained<NSDatePicker>),
+}
+
+fn get_rect(height: f64) -> NSRect {
+    NSRect::new(NSPoint::ZERO, NSSize::new(FORM_WIDTH, height))
+}
+
+/// Creates the control for a field, and the view to show for it
+fn get_control(
+    kind: &BlockingFormDialogFieldKind,
+    label: &str,
+    calendar: &NSCalendar,
+    mtm: MainThreadMarker,
+) -> (Control, Retained<NSView>) {
+    match kind {
+        BlockingFormDialogFieldKind::Text => {
+            let text_field =
+                NSTextField::initWithFrame(NSTextField::alloc(mtm), get_rect(CONTROL_HEIGHT));
+            let view = Retained::into_super(Retained::into_super(text_field.clone()));
+            (Control::Text(text_field), view)
+        }
+        BlockingFormDialogFieldKind::Password => {
+            let text_field = NSSecureTextField::initWithFrame(
+                NSSecureTextField::alloc(mtm),
+                get_rect(CONTROL_HEIGHT),
+            );
+            let view = Retained::into_super(Retained::into_super(Retained::into_super(
+                text_field.clone(),
+            )));
+            (Control::Password(text_field), view)
+        }
+        BlockingFormDialogFieldKind::Multiline => {
+            let scroll_view =
+                NSScrollView::initWithFrame(NSScrollView::alloc(mtm), get_rect(MULTILINE_HEIGHT));
+            scroll_view.setHasVerticalScroller(true);
+
+            let text_view =
+                NSTextView::initWithFrame(NSTextView::alloc(mtm), get_rect(MULTILINE_HEIGHT));
+            text_view.setRichText(false);
+            scroll_view.setDocumentView(Some(&text_view));
+
+            (
+                Control::Multiline(text_view),
+                Retained::into_super(scroll_view),
+            )
+        }
+        BlockingFormDialogFieldKind::Dropdown(options) => {
+            let pop_up = NSPopUpButton::initWithFrame_pullsDown(
+                NSPopUpButton::alloc(mtm),
+                get_rect(CONTROL_HEIGHT),
+                false,
+            );
+            let titles = options
+                .iter()
+                .map(|option| NSString::from_str(option))
+                .collect::<Vec<_>>();
+            pop_up.addItemsWithTitles(&NSArray::from_retained_slice(&titles));
+
+            let view =
+                Retained::into_super(Retained::into_super(Retained::into_super(pop_up.clone())));
+            (Control::Dropdown(pop_up), view)
+        }
+        BlockingFormDialogFieldKind::Checkbox => {
+            let checkbox = unsafe {
+                NSButton::checkboxWithTitle_target_action(
+                    &NSString::from_str(label),
+                    None,
+                    None,
+                    mtm,
+                )
+            };
+            let view = Retained::into_super(Retained::into_super(checkbox.clone()));
+            (Control::Checkbox(checkbox), view)
+        }
+        BlockingFormDialogFieldKind::Date => {
+            let date_picker = NSDatePicker::new(mtm);
+            date_picker.setDatePickerStyle(NSDatePickerStyle::TextFieldAndStepper);
+            date_picker.setDatePickerElements(NSDatePickerElementFlags::YearMonthDay);
+            date_picker.setCalendar(Some(calendar));
+            date_picker.setDateValue(&NSDate::now());
+            date_picker.setFrameSize(NSSize::new(FORM_WIDTH, CONTROL_HEIGHT));
+
+            let view = Retained::into_super(Retained::into_super(date_picker.clone()));
+            (Control::Date(date_picker), view)
+        }
+    }
+}
+
+fn get_value(control: &Control, calendar: &NSCalendar) -> BlockingFormDialogValue {
+    match control {
+        Control::Text(text_field) => {
+            BlockingFormDialogValue::Text(text_field.stringValue().to_string())
+        }
+        Control::Password(text_field) => {
+            BlockingFormDialogValue::Password(Secret::new(text_field.stringValue().to_string()))
+        }
+        Control::Multiline(text_view) => {
+            BlockingFormDialogValue::Text(text_view.string().to_string())
+        }
+        Control::Dropdown(pop_up) => {
+            BlockingFormDialogValue::Dropdown(pop_up.indexOfSelectedItem().max(0) as usize)
+        }
+        Control::Checkbox(checkbox) => {
+            BlockingFormDialogValue::Checkbox(checkbox.state() == NSControlStateValueOn)
+        }
+        Control::Date(date_picker) => {
+            let date = get_date(calendar, &date_picker.dateValue());
+            BlockingFormDialogValue::Date(date.year, date.month, date.day)
+        }
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingFormDialog<'a, W> {
+    pub(crate) fn show_native(
+        &self,
+    ) -> Result<Option<HashMap<String, BlockingFormDialogValue>>, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        let calendar = get_calendar().unwrap_or_else(NSCalendar::currentCalendar);
+
+        // Each field is a label above its control, except checkboxes which
+        // carry their own label
+        let mut views = Vec::new();
+        let mut controls = Vec::new();
+
+        for field in self.fields {
+            if !matches!(field.kind, BlockingFormDialogFieldKind::Checkbox) {
+                let label = NSTextField::labelWithString(&NSString::from_str(field.label), mtm);
+                label.setFrameSize(NSSize::new(FORM_WIDTH, LABEL_HEIGHT));
+                views.push(Retained::into_super(Retained::into_super(label)));
+            }
+
+            let (control, view) = get_control(&field.kind, field.label, &calendar, mtm);
+            views.push(view);
+            controls.push(control);
+        }
+
+        let height = views
+            .iter()
+            .map(|view| view.frame().size.height)
+            .sum::<f64>()
+            + FIELD_GAP * views.len().saturating_sub(1) as f64;
+        let stack = NSView::initWithFrame(NSView::alloc(mtm), get_rect(height));
+
+        // The origin is at the bottom, so the first field goes on top
+        let mut y = height;
+        for view in &views {
+            y -= view.frame().size.height;
+            view.setFrameOrigin(NSPoint::new(0.0, y));
+            stack.addSubview(view);
+            y -= FIELD_GAP;
+        }
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAccessoryView(Some(&stack));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("OK"));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        let values = (resp == NSAlertFirstButtonReturn).then(|| {
+            self.fields
+                .iter()
+                .zip(&controls)
+                .map(|(field, control)| (field.id.to_string(), get_value(control, &calendar)))
+                .collect()
+        });
+
+        // Don't leave passwords in the fields' own storage, even if cancelled
+        for control in &controls {
+            if let Control::Password(text_field) = control {
+                text_field.setStringValue(ns_string!(""));
+            }
+        }
+
+        Ok(values)
+    }
+}
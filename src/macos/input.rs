@@ -0,0 +1,50 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_ns_window, run_modal};
+use crate::{BlockingDialogError, BlockingInputDialog};
+use objc2::MainThreadMarker;
+use objc2_app_kit::{NSAlert, NSAlertFirstButtonReturn, NSTextField};
+use objc2_foundation::{NSSize, NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingInputDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<String>, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        let text_field = NSTextField::textFieldWithString(
+            &NSString::from_str(self.default_text.unwrap_or("")),
+            mtm,
+        );
+        text_field.setFrameSize(NSSize::new(300.0, 24.0));
+
+        if let Some(placeholder) = self.placeholder {
+            text_field.setPlaceholderString(Some(&NSString::from_str(placeholder)));
+        }
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAccessoryView(Some(&text_field));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("OK"));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        // Type straight into the field instead of focusing the buttons
+        ns_alert.layout();
+        ns_alert
+            .window()
+            .setInitialFirstResponder(Some(&text_field));
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        if resp == NSAlertFirstButtonReturn {
+            Ok(Some(text_field.stringValue().to_string()))
+        } else {
+            Ok(None)
+        }
+    }
+}
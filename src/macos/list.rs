@@ -0,0 +1,170 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_ns_window, run_modal};
+use crate::{BlockingDialogError, BlockingListDialog, BlockingListDialogMode};
+use objc2::rc::Retained;
+use objc2::{MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{
+    NSAlert, NSAlertFirstButtonReturn, NSButton, NSControlStateValueOff, NSControlStateValueOn,
+    NSPopUpButton, NSScrollView, NSTextField, NSView,
+};
+use objc2_foundation::{NSArray, NSPoint, NSRect, NSSize, NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+const LIST_WIDTH: f64 = 300.0;
+const LABEL_HEIGHT: f64 = 17.0;
+const CONTROL_HEIGHT: f64 = 24.0;
+const ROW_GAP: f64 = 4.0;
+/// Taller checklists scroll
+const MAX_LIST_HEIGHT: f64 = 240.0;
+
+enum Control {
+    PopUp(Retained<NSPopUpButton>),
+    Checkboxes(Vec<Retained<NSButton>>),
+}
+
+fn get_rect(height: f64) -> NSRect {
+    NSRect::new(NSPoint::ZERO, NSSize::new(LIST_WIDTH, height))
+}
+
+/// Stacks the views top-down in a new view
+fn get_stack(views: &[Retained<NSView>], mtm: MainThreadMarker) -> Retained<NSView> {
+    let height = views
+        .iter()
+        .map(|view| view.frame().size.height)
+        .sum::<f64>()
+        + ROW_GAP * views.len().saturating_sub(1) as f64;
+    let stack = NSView::initWithFrame(NSView::alloc(mtm), get_rect(height));
+
+    // The origin is at the bottom, so the first view goes on top
+    let mut y = height;
+    for view in views {
+        y -= view.frame().size.height;
+        view.setFrameOrigin(NSPoint::new(0.0, y));
+        stack.addSubview(view);
+        y -= ROW_GAP;
+    }
+
+    stack
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingListDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Vec<usize>, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        let selected = self.get_selected();
+
+        // There's no table in an alert, so the cells of each row are joined
+        let titles = self
+            .rows
+            .iter()
+            .map(|row| NSString::from_str(&row.join(" - ")))
+            .collect::<Vec<_>>();
+
+        let mut views = Vec::new();
+
+        if !self.columns.is_empty() {
+            let header =
+                NSTextField::labelWithString(&NSString::from_str(&self.columns.join(" - ")), mtm);
+            header.setFrameSize(NSSize::new(LIST_WIDTH, LABEL_HEIGHT));
+            views.push(Retained::into_super(Retained::into_super(header)));
+        }
+
+        // Radio and plain lists pick a single row, which is what a pop-up does
+        let control = match self.mode {
+            BlockingListDialogMode::Checklist => {
+                let checkboxes = titles
+                    .iter()
+                    .enumerate()
+                    .map(|(i, title)| {
+                        let checkbox = unsafe {
+                            NSButton::checkboxWithTitle_target_action(title, None, None, mtm)
+                        };
+                        checkbox.setFrameSize(NSSize::new(LIST_WIDTH, CONTROL_HEIGHT));
+
+                        if selected.contains(&i) {
+                            checkbox.setState(NSControlStateValueOn);
+                        } else {
+                            checkbox.setState(NSControlStateValueOff);
+                        }
+
+                        checkbox
+                    })
+                    .collect::<Vec<_>>();
+
+                let rows = checkboxes
+                    .iter()
+                    .map(|checkbox| Retained::into_super(Retained::into_super(checkbox.clone())))
+                    .collect::<Vec<_>>();
+                let rows = get_stack(&rows, mtm);
+
+                if rows.frame().size.height > MAX_LIST_HEIGHT {
+                    let scroll_view = NSScrollView::initWithFrame(
+                        NSScrollView::alloc(mtm),
+                        get_rect(MAX_LIST_HEIGHT),
+                    );
+                    scroll_view.setHasVerticalScroller(true);
+                    scroll_view.setDocumentView(Some(&rows));
+                    views.push(Retained::into_super(scroll_view));
+                } else {
+                    views.push(rows);
+                }
+
+                Control::Checkboxes(checkboxes)
+            }
+            BlockingListDialogMode::Radio | BlockingListDialogMode::Plain => {
+                let pop_up = NSPopUpButton::initWithFrame_pullsDown(
+                    NSPopUpButton::alloc(mtm),
+                    get_rect(CONTROL_HEIGHT),
+                    false,
+                );
+                pop_up.addItemsWithTitles(&NSArray::from_retained_slice(&titles));
+
+                if let Some(i) = selected.first() {
+                    pop_up.selectItemAtIndex(*i as isize);
+                }
+
+                views.push(Retained::into_super(Retained::into_super(
+                    Retained::into_super(pop_up.clone()),
+                )));
+
+                Control::PopUp(pop_up)
+            }
+        };
+
+        let stack = get_stack(&views, mtm);
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAccessoryView(Some(&stack));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("OK"));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        if resp != NSAlertFirstButtonReturn {
+            return Ok(Vec::new());
+        }
+
+        let selected = match control {
+            Control::PopUp(pop_up) => match pop_up.indexOfSelectedItem() {
+                -1 => Vec::new(),
+                i => vec![i as usize],
+            },
+            Control::Checkboxes(checkboxes) => checkboxes
+                .iter()
+                .enumerate()
+                .filter(|(_, checkbox)| checkbox.state() == NSControlStateValueOn)
+                .map(|(i, _)| i)
+                .collect(),
+        };
+
+        Ok(selected)
+    }
+}
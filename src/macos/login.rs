@@ -0,0 +1,71 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_ns_window, run_modal};
+use crate::{BlockingDialogError, BlockingLoginDialog, Credentials, Secret};
+use objc2::{MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{NSAlert, NSAlertFirstButtonReturn, NSSecureTextField, NSTextField, NSView};
+use objc2_foundation::{NSPoint, NSRect, NSSize, NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+const FIELD_WIDTH: f64 = 300.0;
+const FIELD_HEIGHT: f64 = 24.0;
+const FIELD_GAP: f64 = 8.0;
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingLoginDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Credentials>, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        let username_field =
+            NSTextField::textFieldWithString(&NSString::from_str(self.username.unwrap_or("")), mtm);
+        username_field.setPlaceholderString(Some(ns_string!("Username")));
+        username_field.setFrameSize(NSSize::new(FIELD_WIDTH, FIELD_HEIGHT));
+        username_field.setFrameOrigin(NSPoint::new(0.0, FIELD_HEIGHT + FIELD_GAP));
+
+        let password_field = NSSecureTextField::new(mtm);
+        password_field.setPlaceholderString(Some(ns_string!("Password")));
+        password_field.setFrameSize(NSSize::new(FIELD_WIDTH, FIELD_HEIGHT));
+        unsafe { username_field.setNextKeyView(Some(&password_field)) };
+
+        // The accessory view's origin is at the bottom, so the username goes on top
+        let size = NSSize::new(FIELD_WIDTH, 2.0 * FIELD_HEIGHT + FIELD_GAP);
+        let fields = NSView::initWithFrame(NSView::alloc(mtm), NSRect::new(NSPoint::ZERO, size));
+        fields.addSubview(&username_field);
+        fields.addSubview(&password_field);
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAccessoryView(Some(&fields));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("OK"));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        // Start on the password when the username is already filled in
+        ns_alert.layout();
+        if self.username.is_some() {
+            ns_alert
+                .window()
+                .setInitialFirstResponder(Some(&password_field));
+        } else {
+            ns_alert
+                .window()
+                .setInitialFirstResponder(Some(&username_field));
+        }
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        let credentials = (resp == NSAlertFirstButtonReturn).then(|| Credentials {
+            username: username_field.stringValue().to_string(),
+            password: Secret::new(password_field.stringValue().to_string()),
+        });
+
+        // Don't leave the password in the field's own storage, even if cancelled
+        password_field.setStringValue(ns_string!(""));
+
+        Ok(credentials)
+    }
+}
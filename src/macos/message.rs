@@ -0,0 +1,58 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_ns_alert_icon, get_ns_alert_style, get_ns_window, run_modal};
+use crate::{BlockingDialogError, BlockingMessageDialog};
+use objc2::MainThreadMarker;
+use objc2_app_kit::{NSAlert, NSAlertFirstButtonReturn};
+use objc2_foundation::{NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingMessageDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<usize, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        let buttons = self.get_buttons();
+        let default_button = self.get_default_button();
+        let cancel_button = self.get_cancel_button();
+
+        let style = get_ns_alert_style(self.level);
+        let icon = get_ns_alert_icon(self.level);
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAlertStyle(style);
+
+        // NSAlert gives Return to the first button, move it to the default
+        // one and Escape to the cancel one
+        for (i, label) in buttons.iter().enumerate() {
+            let ns_button = ns_alert.addButtonWithTitle(&NSString::from_str(label));
+
+            if i == default_button {
+                ns_button.setKeyEquivalent(ns_string!("\r"));
+            } else if i == cancel_button {
+                ns_button.setKeyEquivalent(ns_string!("\u{1b}"));
+            } else {
+                ns_button.setKeyEquivalent(ns_string!(""));
+            }
+        }
+
+        if let Some(icon) = icon {
+            unsafe { ns_alert.setIcon(Some(icon.as_ref())) }
+        }
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        let index = usize::try_from(resp - NSAlertFirstButtonReturn)
+            .ok()
+            .filter(|index| *index < buttons.len())
+            .unwrap_or(cancel_button);
+
+        Ok(index)
+    }
+}
@@ -2,7 +2,132 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 pub mod alert;
+pub mod color;
 pub mod confirm;
+pub mod date;
+pub mod form;
+pub mod input;
+pub mod list;
+pub mod login;
+pub mod message;
+pub mod password;
 pub mod pick_directory;
 pub mod pick_files;
+pub mod progress;
+pub mod question;
 pub mod save_file;
+
+use crate::{BlockingDialogError, BlockingDialogLevel};
+use block2::{DynBlock, RcBlock};
+use objc2::MainThreadMarker;
+use objc2::rc::Retained;
+use objc2_app_kit::{
+    NSAlert, NSAlertStyle, NSApplication, NSImage, NSModalResponse, NSOpenPanel, NSSavePanel,
+    NSView, NSWindow,
+};
+use objc2_foundation::NSString;
+use raw_window_handle::{HandleError, HasWindowHandle, RawWindowHandle};
+
+pub fn get_ns_alert_style(level: BlockingDialogLevel) -> NSAlertStyle {
+    match level {
+        BlockingDialogLevel::Info => NSAlertStyle::Informational,
+        BlockingDialogLevel::Warning => NSAlertStyle::Warning,
+        BlockingDialogLevel::Error => NSAlertStyle::Warning,
+    }
+}
+
+pub fn get_ns_alert_icon(level: BlockingDialogLevel) -> Option<Retained<NSImage>> {
+    match level {
+        BlockingDialogLevel::Info => NSImage::imageWithSystemSymbolName_accessibilityDescription(
+            &NSString::from_str("info.circle"),
+            Some(&NSString::from_str("Info")),
+        ),
+        BlockingDialogLevel::Warning => {
+            NSImage::imageWithSystemSymbolName_accessibilityDescription(
+                &NSString::from_str("exclamationmark.triangle"),
+                Some(&NSString::from_str("Warning")),
+            )
+        }
+        BlockingDialogLevel::Error => NSImage::imageWithSystemSymbolName_accessibilityDescription(
+            &NSString::from_str("multiply.circle"),
+            Some(&NSString::from_str("Error")),
+        ),
+    }
+}
+
+/// The window to attach the sheet to, `None` for `NoParent`
+pub fn get_ns_window<W: HasWindowHandle>(
+    window: &W,
+) -> Result<Option<Retained<NSWindow>>, BlockingDialogError> {
+    let w = match window.window_handle() {
+        Ok(w) => w,
+        Err(HandleError::Unavailable) => return Ok(None),
+        Err(err) => return Err(BlockingDialogError::Handle(err)),
+    };
+
+    let RawWindowHandle::AppKit(w) = w.as_raw() else {
+        return Err(BlockingDialogError::Handle(HandleError::NotSupported));
+    };
+
+    let ns_view = unsafe { w.ns_view.cast::<NSView>().as_ref() };
+    let Some(ns_window) = ns_view.window() else {
+        return Err(BlockingDialogError::Handle(HandleError::Unavailable));
+    };
+
+    Ok(Some(ns_window))
+}
+
+/// An alert or panel that can run on its own or as a sheet
+pub trait Modal {
+    fn begin_sheet(&self, ns_window: &NSWindow, handler: &DynBlock<dyn Fn(NSModalResponse)>);
+    fn run(&self) -> NSModalResponse;
+}
+
+impl Modal for NSAlert {
+    fn begin_sheet(&self, ns_window: &NSWindow, handler: &DynBlock<dyn Fn(NSModalResponse)>) {
+        self.beginSheetModalForWindow_completionHandler(ns_window, Some(handler));
+    }
+
+    fn run(&self) -> NSModalResponse {
+        self.runModal()
+    }
+}
+
+impl Modal for NSSavePanel {
+    fn begin_sheet(&self, ns_window: &NSWindow, handler: &DynBlock<dyn Fn(NSModalResponse)>) {
+        self.beginSheetModalForWindow_completionHandler(ns_window, handler);
+    }
+
+    fn run(&self) -> NSModalResponse {
+        self.runModal()
+    }
+}
+
+impl Modal for NSOpenPanel {
+    fn begin_sheet(&self, ns_window: &NSWindow, handler: &DynBlock<dyn Fn(NSModalResponse)>) {
+        self.beginSheetModalForWindow_completionHandler(ns_window, handler);
+    }
+
+    fn run(&self) -> NSModalResponse {
+        self.runModal()
+    }
+}
+
+/// Runs the alert or panel as a sheet on the window if any, otherwise on its
+/// own, and blocks until it's dismissed
+pub fn run_modal(
+    modal: &impl Modal,
+    ns_window: Option<&NSWindow>,
+    mtm: MainThreadMarker,
+) -> NSModalResponse {
+    let Some(ns_window) = ns_window else {
+        return modal.run();
+    };
+
+    let handler = RcBlock::new(move |resp| {
+        NSApplication::sharedApplication(mtm).stopModalWithCode(resp);
+    });
+
+    modal.begin_sheet(ns_window, &handler);
+    NSApplication::sharedApplication(mtm).runModalForWindow(ns_window)
+}
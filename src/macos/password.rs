@@ -0,0 +1,45 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_ns_window, run_modal};
+use crate::{BlockingDialogError, BlockingPasswordDialog, Secret};
+use objc2::MainThreadMarker;
+use objc2_app_kit::{NSAlert, NSAlertFirstButtonReturn, NSSecureTextField};
+use objc2_foundation::{NSSize, NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPasswordDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Secret>, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        let text_field = NSSecureTextField::new(mtm);
+        text_field.setFrameSize(NSSize::new(300.0, 24.0));
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAccessoryView(Some(&text_field));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("OK"));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        // Type straight into the field instead of focusing the buttons
+        ns_alert.layout();
+        ns_alert
+            .window()
+            .setInitialFirstResponder(Some(&text_field));
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        let secret = (resp == NSAlertFirstButtonReturn)
+            .then(|| Secret::new(text_field.stringValue().to_string()));
+
+        // Don't leave the password in the field's own storage, even if cancelled
+        text_field.setStringValue(ns_string!(""));
+
+        Ok(secret)
+    }
+}
@@ -1,46 +1,35 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use super::{get_ns_window, run_modal};
 use crate::{BlockingDialogError, BlockingPickDirectoryDialog};
-use block2::RcBlock;
 use objc2::MainThreadMarker;
-use objc2_app_kit::{NSApplication, NSModalResponseOK, NSOpenPanel, NSView};
-use objc2_foundation::NSString;
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
+use objc2_app_kit::{NSModalResponseOK, NSOpenPanel};
+use objc2_foundation::{NSString, NSURL};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::path::PathBuf;
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickDirectoryDialog<'a, W> {
-    pub fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+    pub(crate) fn show_native(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
         let Some(mtm) = MainThreadMarker::new() else {
             return Err(BlockingDialogError::NotOnMainThread);
         };
 
         let panel = NSOpenPanel::openPanel(mtm);
         panel.setTitle(Some(&NSString::from_str(self.title)));
+
+        if let Some(dir) = self.starting_directory {
+            let dir = NSString::from_str(&dir.to_string_lossy());
+            panel.setDirectoryURL(Some(&NSURL::fileURLWithPath_isDirectory(&dir, true)));
+        }
+
         panel.setCanChooseFiles(false);
         panel.setCanChooseDirectories(true);
         panel.setAllowsMultipleSelection(false);
 
-        let w = match self.window.window_handle() {
-            Ok(w) => w,
-            Err(err) => return Err(BlockingDialogError::Handle(err)),
-        };
-
-        let RawWindowHandle::AppKit(w) = w.as_raw() else {
-            return Err(BlockingDialogError::Handle(HandleError::NotSupported));
-        };
-
-        let handler = RcBlock::new(move |resp| {
-            NSApplication::sharedApplication(mtm).stopModalWithCode(resp);
-        });
-
-        let ns_view = unsafe { w.ns_view.cast::<NSView>().as_ref() };
-        let Some(ns_window) = ns_view.window() else {
-            return Err(BlockingDialogError::Handle(HandleError::Unavailable));
-        };
+        let ns_window = get_ns_window(&self.window)?;
 
-        panel.beginSheetModalForWindow_completionHandler(&ns_window, &handler);
-        let resp = NSApplication::sharedApplication(mtm).runModalForWindow(&ns_window);
+        let resp = run_modal(&*panel, ns_window.as_deref(), mtm);
 
         let mut paths = Vec::new();
 
@@ -0,0 +1,87 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::progress::POLL_INTERVAL;
+use crate::{BlockingDialogError, BlockingProgressDialog, Progress};
+use objc2::{MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{
+    NSAlert, NSApplication, NSModalResponseContinue, NSProgressIndicator, NSProgressIndicatorStyle,
+};
+use objc2_foundation::{NSPoint, NSRect, NSSize, NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::thread;
+
+const BAR_WIDTH: f64 = 300.0;
+const BAR_HEIGHT: f64 = 20.0;
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingProgressDialog<'a, W> {
+    pub(crate) fn prepare_native(&self) -> Result<MainThreadMarker, BlockingDialogError> {
+        MainThreadMarker::new().ok_or(BlockingDialogError::NotOnMainThread)
+    }
+
+    pub(crate) fn show_native(
+        &self,
+        mtm: MainThreadMarker,
+        progress: &Progress,
+    ) -> Result<(), BlockingDialogError> {
+        let rect = NSRect::new(NSPoint::ZERO, NSSize::new(BAR_WIDTH, BAR_HEIGHT));
+        let bar = NSProgressIndicator::initWithFrame(NSProgressIndicator::alloc(mtm), rect);
+        bar.setStyle(NSProgressIndicatorStyle::Bar);
+        bar.setMinValue(0.0);
+        bar.setMaxValue(1.0);
+        bar.setIndeterminate(false);
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAccessoryView(Some(&bar));
+        let cancel_button = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+        ns_alert.layout();
+
+        // A sheet would close as soon as Cancel is clicked, while this stays
+        // until the worker returns, so the alert is its own modal window
+        let app = NSApplication::sharedApplication(mtm);
+        let window = ns_alert.window();
+        let session = app.beginModalSessionForWindow(&window);
+        let mut shown = None;
+
+        while !progress.is_finished() {
+            let resp = unsafe { app.runModalSession(session) };
+
+            if resp != NSModalResponseContinue && !progress.is_cancelled() {
+                progress.cancel();
+                cancel_button.setEnabled(false);
+            }
+
+            let state = progress.get_state();
+
+            if shown.as_ref() != Some(&state) {
+                if let Some(text) = &state.text {
+                    ns_alert.setInformativeText(&NSString::from_str(text));
+                    ns_alert.layout();
+                }
+
+                match state.fraction {
+                    Some(fraction) => {
+                        unsafe { bar.stopAnimation(None) };
+                        bar.setIndeterminate(false);
+                        bar.setDoubleValue(fraction);
+                    }
+                    None => {
+                        bar.setIndeterminate(true);
+                        unsafe { bar.startAnimation(None) };
+                    }
+                }
+
+                shown = Some(state);
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        unsafe { app.endModalSession(session) };
+        window.orderOut(None);
+
+        Ok(())
+    }
+}
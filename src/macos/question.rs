@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_ns_alert_icon, get_ns_alert_style, get_ns_window, run_modal};
+use crate::{BlockingDialogError, BlockingQuestionDialog, BlockingQuestionDialogResponse};
+use objc2::MainThreadMarker;
+use objc2_app_kit::{NSAlert, NSAlertFirstButtonReturn, NSAlertSecondButtonReturn};
+use objc2_foundation::{NSString, ns_string};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingQuestionDialog<'a, W> {
+    pub(crate) fn show_native(
+        &self,
+    ) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return Err(BlockingDialogError::NotOnMainThread);
+        };
+
+        let style = get_ns_alert_style(self.level);
+        let icon = get_ns_alert_icon(self.level);
+
+        let ns_alert = NSAlert::new(mtm);
+        ns_alert.setMessageText(&NSString::from_str(self.title));
+        ns_alert.setInformativeText(&NSString::from_str(self.message));
+        ns_alert.setAlertStyle(style);
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Yes"));
+        let _ = ns_alert.addButtonWithTitle(ns_string!("No"));
+        // A button titled "Cancel" also answers the Escape key
+        let _ = ns_alert.addButtonWithTitle(ns_string!("Cancel"));
+
+        if let Some(icon) = icon {
+            unsafe { ns_alert.setIcon(Some(icon.as_ref())) }
+        }
+
+        let ns_window = get_ns_window(&self.window)?;
+
+        let resp = run_modal(&*ns_alert, ns_window.as_deref(), mtm);
+
+        let response = if resp == NSAlertFirstButtonReturn {
+            BlockingQuestionDialogResponse::Yes
+        } else if resp == NSAlertSecondButtonReturn {
+            BlockingQuestionDialogResponse::No
+        } else {
+            BlockingQuestionDialogResponse::Cancel
+        };
+
+        Ok(response)
+    }
+}
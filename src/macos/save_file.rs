@@ -1,13 +1,13 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use super::{get_ns_window, run_modal};
 use crate::{BlockingDialogError, BlockingPickFilesDialogFilter, BlockingSaveFileDialog};
-use block2::RcBlock;
 use objc2::{MainThreadMarker, rc::Retained};
-use objc2_app_kit::{NSApplication, NSModalResponseOK, NSSavePanel, NSView};
-use objc2_foundation::{NSArray, NSString};
+use objc2_app_kit::{NSModalResponseOK, NSSavePanel};
+use objc2_foundation::{NSArray, NSString, NSURL};
 use objc2_uniform_type_identifiers::UTType;
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::path::PathBuf;
 
 fn get_filter(filter: &[BlockingPickFilesDialogFilter]) -> Retained<NSArray<UTType>> {
@@ -27,13 +27,19 @@ fn get_filter(filter: &[BlockingPickFilesDialogFilter]) -> Retained<NSArray<UTTy
 }
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingSaveFileDialog<'a, W> {
-    pub fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+    pub(crate) fn show_native(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
         let Some(mtm) = MainThreadMarker::new() else {
             return Err(BlockingDialogError::NotOnMainThread);
         };
 
         let panel = NSSavePanel::savePanel(mtm);
         panel.setTitle(Some(&NSString::from_str(self.title)));
+
+        if let Some(dir) = self.starting_directory {
+            let dir = NSString::from_str(&dir.to_string_lossy());
+            panel.setDirectoryURL(Some(&NSURL::fileURLWithPath_isDirectory(&dir, true)));
+        }
+
         panel.setAllowedContentTypes(&get_filter(self.filter));
 
         if let Some(default_filename) = &self.default_filename {
@@ -41,26 +47,9 @@ impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingSaveFileDialog<'a, W> {
             panel.setNameFieldStringValue(&default_filename);
         }
 
-        let w = match self.window.window_handle() {
-            Ok(w) => w,
-            Err(err) => return Err(BlockingDialogError::Handle(err)),
-        };
-
-        let RawWindowHandle::AppKit(w) = w.as_raw() else {
-            return Err(BlockingDialogError::Handle(HandleError::NotSupported));
-        };
-
-        let handler = RcBlock::new(move |resp| {
-            NSApplication::sharedApplication(mtm).stopModalWithCode(resp);
-        });
-
-        let ns_view = unsafe { w.ns_view.cast::<NSView>().as_ref() };
-        let Some(ns_window) = ns_view.window() else {
-            return Err(BlockingDialogError::Handle(HandleError::Unavailable));
-        };
+        let ns_window = get_ns_window(&self.window)?;
 
-        panel.beginSheetModalForWindow_completionHandler(&ns_window, &handler);
-        let resp = NSApplication::sharedApplication(mtm).runModalForWindow(&ns_window);
+        let resp = run_modal(&*panel, ns_window.as_deref(), mtm);
 
         if resp == NSModalResponseOK
             && let Some(url) = panel.URL()
@@ -0,0 +1,603 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Canned dialog responses for unit tests, enabled by the `mock` feature.
+//!
+//! Responses are queued per thread and consumed in order by every dialog's
+//! `show()`, which also records the request it received. Showing a dialog
+//! with nothing queued panics, so a test never opens a real one.
+//!
+//! ```
+//! use blocking_dialog::mock::{self, Request, Response};
+//! use blocking_dialog::{BlockingConfirmDialog, NoParent};
+//!
+//! mock::push(Response::Confirm(true));
+//! let dialog = BlockingConfirmDialog::new(NoParent).title("Delete");
+//! assert!(dialog.show().unwrap());
+//!
+//! let requests = mock::take_requests();
+//! assert!(matches!(&requests[..], [Request::Confirm { title, .. }] if title == "Delete"));
+//! ```
+
+use crate::{
+    BlockingDialogError, BlockingDialogLevel, BlockingFormDialogField, BlockingFormDialogFieldKind,
+    BlockingFormDialogValue, BlockingListDialogMode, BlockingPickFilesDialogFilter,
+    BlockingQuestionDialogResponse, Credentials, Date, Rgba, Secret,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+
+thread_local! {
+    static RESPONSES: RefCell<VecDeque<Response>> = const { RefCell::new(VecDeque::new()) };
+    static REQUESTS: RefCell<Vec<Request>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Debug)]
+pub enum Response {
+    /// Dismisses an alert
+    Ok,
+    Confirm(bool),
+    Question(BlockingQuestionDialogResponse),
+    /// Clicks the button at this index of a message dialog
+    Button(usize),
+    /// Submits this text from an input or password dialog
+    Text(String),
+    /// Fills in and submits a login dialog
+    Login {
+        username: String,
+        password: String,
+    },
+    /// Submits a form dialog with these values
+    Form(HashMap<String, BlockingFormDialogValue>),
+    /// Selects these rows of a list dialog
+    Rows(Vec<usize>),
+    /// Picks this color
+    Color(Rgba),
+    /// Picks this date
+    Date(Date),
+    /// Selects these paths, dialogs returning a single path take the first one
+    Files(Vec<PathBuf>),
+    /// Dismisses any dialog as if the user cancelled it
+    Cancel,
+    Error(BlockingDialogError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub name: String,
+    pub extensions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub id: String,
+    pub label: String,
+    pub kind: FieldKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    Text,
+    Password,
+    Multiline,
+    Dropdown(Vec<String>),
+    Checkbox,
+    Date,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    Alert {
+        title: String,
+        message: String,
+        level: BlockingDialogLevel,
+    },
+    Confirm {
+        title: String,
+        message: String,
+        level: BlockingDialogLevel,
+        accept_label: Option<String>,
+        cancel_label: Option<String>,
+    },
+    Question {
+        title: String,
+        message: String,
+        level: BlockingDialogLevel,
+    },
+    Message {
+        title: String,
+        message: String,
+        level: BlockingDialogLevel,
+        buttons: Vec<String>,
+        default_button: usize,
+        cancel_button: usize,
+    },
+    Input {
+        title: String,
+        message: String,
+        default_text: Option<String>,
+        placeholder: Option<String>,
+    },
+    Password {
+        title: String,
+        message: String,
+    },
+    Login {
+        title: String,
+        message: String,
+        username: Option<String>,
+    },
+    Form {
+        title: String,
+        message: String,
+        fields: Vec<Field>,
+    },
+    List {
+        title: String,
+        message: String,
+        columns: Vec<String>,
+        rows: Vec<Vec<String>>,
+        selected: Vec<usize>,
+        mode: BlockingListDialogMode,
+    },
+    Color {
+        title: String,
+        initial: Option<Rgba>,
+        alpha: bool,
+    },
+    Date {
+        title: String,
+        message: String,
+        initial: Option<Date>,
+        min: Option<Date>,
+        max: Option<Date>,
+    },
+    Progress {
+        title: String,
+        message: String,
+    },
+    PickFiles {
+        title: String,
+        multiple: bool,
+        filter: Vec<Filter>,
+        starting_directory: Option<PathBuf>,
+    },
+    PickDirectory {
+        title: String,
+        starting_directory: Option<PathBuf>,
+    },
+    SaveFile {
+        title: String,
+        default_filename: Option<String>,
+        filter: Vec<Filter>,
+        starting_directory: Option<PathBuf>,
+    },
+}
+
+impl Request {
+    /// The kind of dialog, for panic messages
+    fn dialog(&self) -> &'static str {
+        match self {
+            Request::Alert { .. } => "alert",
+            Request::Confirm { .. } => "confirm",
+            Request::Question { .. } => "question",
+            Request::Message { .. } => "message",
+            Request::Input { .. } => "input",
+            Request::Password { .. } => "password",
+            Request::Login { .. } => "login",
+            Request::Form { .. } => "form",
+            Request::List { .. } => "list",
+            Request::Color { .. } => "color",
+            Request::Date { .. } => "date",
+            Request::Progress { .. } => "progress",
+            Request::PickFiles { .. } => "pick files",
+            Request::PickDirectory { .. } => "pick directory",
+            Request::SaveFile { .. } => "save file",
+        }
+    }
+}
+
+/// Queues a response for the next dialog shown on this thread
+pub fn push(response: Response) {
+    RESPONSES.with_borrow_mut(|responses| responses.push_back(response));
+}
+
+/// Returns the requests recorded on this thread so far, and clears them
+pub fn take_requests() -> Vec<Request> {
+    REQUESTS.with_borrow_mut(std::mem::take)
+}
+
+/// Clears both queued responses and recorded requests
+pub fn reset() {
+    RESPONSES.with_borrow_mut(VecDeque::clear);
+    REQUESTS.with_borrow_mut(Vec::clear);
+}
+
+pub(crate) fn get_filter(filter: &[BlockingPickFilesDialogFilter]) -> Vec<Filter> {
+    filter
+        .iter()
+        .map(|entry| Filter {
+            name: entry.name.to_string(),
+            extensions: entry.extensions.iter().map(|ext| ext.to_string()).collect(),
+        })
+        .collect()
+}
+
+pub(crate) fn get_fields(fields: &[BlockingFormDialogField]) -> Vec<Field> {
+    fields
+        .iter()
+        .map(|field| Field {
+            id: field.id.to_string(),
+            label: field.label.to_string(),
+            kind: match field.kind {
+                BlockingFormDialogFieldKind::Text => FieldKind::Text,
+                BlockingFormDialogFieldKind::Password => FieldKind::Password,
+                BlockingFormDialogFieldKind::Multiline => FieldKind::Multiline,
+                BlockingFormDialogFieldKind::Dropdown(options) => {
+                    FieldKind::Dropdown(options.iter().map(|o| o.to_string()).collect())
+                }
+                BlockingFormDialogFieldKind::Checkbox => FieldKind::Checkbox,
+                BlockingFormDialogFieldKind::Date => FieldKind::Date,
+            },
+        })
+        .collect()
+}
+
+/// Records the request and pops the next queued response, panicking if there
+/// is none
+pub(crate) fn respond(request: Request) -> Response {
+    let dialog = request.dialog();
+    REQUESTS.with_borrow_mut(|requests| requests.push(request));
+
+    match RESPONSES.with_borrow_mut(VecDeque::pop_front) {
+        Some(response) => response,
+        None => panic!("no mock response queued for {dialog} dialog"),
+    }
+}
+
+fn unexpected(response: Response, dialog: &str) -> ! {
+    panic!("mock response {response:?} can't answer a {dialog} dialog")
+}
+
+impl Response {
+    pub(crate) fn into_alert(self) -> Result<(), BlockingDialogError> {
+        match self {
+            Response::Ok | Response::Cancel => Ok(()),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "alert"),
+        }
+    }
+
+    pub(crate) fn into_confirm(self) -> Result<bool, BlockingDialogError> {
+        match self {
+            Response::Confirm(yes) => Ok(yes),
+            Response::Ok => Ok(true),
+            Response::Cancel => Ok(false),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "confirm"),
+        }
+    }
+
+    pub(crate) fn into_question(
+        self,
+    ) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+        match self {
+            Response::Question(answer) => Ok(answer),
+            Response::Confirm(true) | Response::Ok => Ok(BlockingQuestionDialogResponse::Yes),
+            Response::Confirm(false) => Ok(BlockingQuestionDialogResponse::No),
+            Response::Cancel => Ok(BlockingQuestionDialogResponse::Cancel),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "question"),
+        }
+    }
+
+    /// `Ok` clicks the default button and `Cancel` the cancel one
+    pub(crate) fn into_button(
+        self,
+        default_button: usize,
+        cancel_button: usize,
+    ) -> Result<usize, BlockingDialogError> {
+        match self {
+            Response::Button(index) => Ok(index),
+            Response::Ok => Ok(default_button),
+            Response::Cancel => Ok(cancel_button),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "message"),
+        }
+    }
+
+    /// `Ok` submits the default text unchanged
+    pub(crate) fn into_text(
+        self,
+        default_text: Option<&str>,
+    ) -> Result<Option<String>, BlockingDialogError> {
+        match self {
+            Response::Text(text) => Ok(Some(text)),
+            Response::Ok => Ok(Some(default_text.unwrap_or_default().to_string())),
+            Response::Cancel => Ok(None),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "input"),
+        }
+    }
+
+    pub(crate) fn into_secret(self) -> Result<Option<Secret>, BlockingDialogError> {
+        match self {
+            Response::Text(text) => Ok(Some(Secret::new(text))),
+            Response::Cancel => Ok(None),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "password"),
+        }
+    }
+
+    pub(crate) fn into_credentials(self) -> Result<Option<Credentials>, BlockingDialogError> {
+        match self {
+            Response::Login { username, password } => Ok(Some(Credentials {
+                username,
+                password: Secret::new(password),
+            })),
+            Response::Cancel => Ok(None),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "login"),
+        }
+    }
+
+    pub(crate) fn into_form(
+        self,
+    ) -> Result<Option<HashMap<String, BlockingFormDialogValue>>, BlockingDialogError> {
+        match self {
+            Response::Form(values) => Ok(Some(values)),
+            Response::Cancel => Ok(None),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "form"),
+        }
+    }
+
+    /// `Ok` keeps the preselected rows
+    pub(crate) fn into_rows(self, selected: Vec<usize>) -> Result<Vec<usize>, BlockingDialogError> {
+        match self {
+            Response::Rows(rows) => Ok(rows),
+            Response::Ok => Ok(selected),
+            Response::Cancel => Ok(Vec::new()),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "list"),
+        }
+    }
+
+    /// `Ok` picks the initial color unchanged, or black without one
+    pub(crate) fn into_color(
+        self,
+        initial: Option<Rgba>,
+    ) -> Result<Option<Rgba>, BlockingDialogError> {
+        match self {
+            Response::Color(color) => Ok(Some(color)),
+            Response::Ok => Ok(Some(initial.unwrap_or(Rgba::rgb(0, 0, 0)))),
+            Response::Cancel => Ok(None),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "color"),
+        }
+    }
+
+    /// `Ok` picks the initial date unchanged, which must have been set
+    pub(crate) fn into_date(
+        self,
+        initial: Option<Date>,
+    ) -> Result<Option<Date>, BlockingDialogError> {
+        match self {
+            Response::Date(date) => Ok(Some(date)),
+            Response::Ok if initial.is_some() => Ok(initial),
+            Response::Cancel => Ok(None),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "date"),
+        }
+    }
+
+    /// `Ok` lets the worker run to the end, `Cancel` runs it already cancelled
+    pub(crate) fn into_cancelled(self) -> Result<bool, BlockingDialogError> {
+        match self {
+            Response::Ok => Ok(false),
+            Response::Cancel => Ok(true),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "progress"),
+        }
+    }
+
+    pub(crate) fn into_files(self) -> Result<Vec<PathBuf>, BlockingDialogError> {
+        match self {
+            Response::Files(paths) => Ok(paths),
+            Response::Cancel => Ok(Vec::new()),
+            Response::Error(err) => Err(err),
+            response => unexpected(response, "file"),
+        }
+    }
+
+    pub(crate) fn into_file(self) -> Result<Option<PathBuf>, BlockingDialogError> {
+        self.into_files().map(|paths| paths.into_iter().next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BlockingAlertDialog, BlockingColorDialog, BlockingConfirmDialog, BlockingFormDialog,
+        BlockingInputDialog, BlockingListDialog, BlockingMessageDialog,
+        BlockingPickDirectoryDialog, BlockingPickFilesDialog, BlockingProgressDialog,
+        BlockingProgressDialogOutcome, BlockingSaveFileDialog, NoParent,
+    };
+    use std::io;
+    use std::path::Path;
+
+    const FILTER: &[BlockingPickFilesDialogFilter] = &[BlockingPickFilesDialogFilter {
+        name: "Images",
+        extensions: &["png", "jpg"],
+    }];
+
+    fn get_images_filter() -> Vec<Filter> {
+        vec![Filter {
+            name: "Images".to_string(),
+            extensions: vec!["png".to_string(), "jpg".to_string()],
+        }]
+    }
+
+    #[test]
+    fn confirms() {
+        push(Response::Confirm(false));
+        push(Response::Ok);
+
+        let dialog = BlockingConfirmDialog::new(NoParent)
+            .title("Delete")
+            .message("Delete it?")
+            .level(BlockingDialogLevel::Warning)
+            .accept_label("Delete");
+        assert!(!dialog.show().unwrap());
+        assert!(dialog.show().unwrap());
+
+        let request = Request::Confirm {
+            title: "Delete".to_string(),
+            message: "Delete it?".to_string(),
+            level: BlockingDialogLevel::Warning,
+            accept_label: Some("Delete".to_string()),
+            cancel_label: None,
+        };
+        assert_eq!(take_requests(), [request.clone(), request]);
+        assert!(take_requests().is_empty());
+    }
+
+    #[test]
+    fn picks_files() {
+        let paths = vec![PathBuf::from("/tmp/a.png"), PathBuf::from("/tmp/b.jpg")];
+        push(Response::Files(paths.clone()));
+        push(Response::Files(paths.clone()));
+        push(Response::Files(Vec::new()));
+
+        let dialog = BlockingPickFilesDialog::new(NoParent)
+            .title("Open")
+            .multiple(true)
+            .filter(FILTER)
+            .starting_directory(Path::new("/tmp"));
+        assert_eq!(dialog.show().unwrap(), paths);
+
+        // Dialogs returning a single path take the first one
+        let dialog = BlockingSaveFileDialog::new(NoParent)
+            .title("Save")
+            .default_filename("a.png")
+            .filter(FILTER);
+        assert_eq!(dialog.show().unwrap(), Some(paths[0].clone()));
+
+        let dialog = BlockingPickDirectoryDialog::new(NoParent).title("Folder");
+        assert_eq!(dialog.show().unwrap(), None);
+
+        let requests = [
+            Request::PickFiles {
+                title: "Open".to_string(),
+                multiple: true,
+                filter: get_images_filter(),
+                starting_directory: Some(PathBuf::from("/tmp")),
+            },
+            Request::SaveFile {
+                title: "Save".to_string(),
+                default_filename: Some("a.png".to_string()),
+                filter: get_images_filter(),
+                starting_directory: None,
+            },
+            Request::PickDirectory {
+                title: "Folder".to_string(),
+                starting_directory: None,
+            },
+        ];
+        assert_eq!(take_requests(), requests);
+    }
+
+    #[test]
+    fn cancels() {
+        for _ in 0..6 {
+            push(Response::Cancel);
+        }
+
+        let dialog = BlockingAlertDialog::new(NoParent).title("Done");
+        dialog.show().unwrap();
+
+        let dialog = BlockingInputDialog::new(NoParent).default_text("name");
+        assert_eq!(dialog.show().unwrap(), None);
+
+        let dialog = BlockingMessageDialog::new(NoParent)
+            .buttons(&["Save", "Discard", "Cancel"])
+            .cancel_button(2);
+        assert_eq!(dialog.show().unwrap(), 2);
+
+        let dialog = BlockingListDialog::new(NoParent)
+            .rows(&[&["a"], &["b"]])
+            .selected(&[1]);
+        assert!(dialog.show().unwrap().is_empty());
+
+        let dialog = BlockingColorDialog::new(NoParent);
+        assert_eq!(dialog.show().unwrap(), None);
+
+        let dialog = BlockingProgressDialog::new(NoParent).title("Copying");
+        let outcome = dialog.run(|progress| progress.is_cancelled()).unwrap();
+        assert!(matches!(outcome, BlockingProgressDialogOutcome::Cancelled));
+
+        let requests = take_requests();
+        assert_eq!(requests.len(), 6);
+        assert!(matches!(&requests[0], Request::Alert { title, .. } if title == "Done"));
+        assert!(matches!(
+            &requests[5],
+            Request::Progress { title, .. } if title == "Copying"
+        ));
+    }
+
+    #[test]
+    fn fails() {
+        push(Response::Error(BlockingDialogError::NoBackend(
+            "zenity".to_string(),
+        )));
+
+        let dialog = BlockingPickFilesDialog::new(NoParent).title("Open");
+        let err = dialog.show().unwrap_err();
+        assert!(matches!(err, BlockingDialogError::NoBackend(tried) if tried == "zenity"));
+        assert_eq!(take_requests().len(), 1);
+    }
+
+    #[test]
+    fn resets() {
+        push(Response::Ok);
+        BlockingAlertDialog::new(NoParent).show().unwrap();
+        push(Response::Ok);
+
+        reset();
+        assert!(take_requests().is_empty());
+        assert!(RESPONSES.with_borrow(VecDeque::is_empty));
+    }
+
+    #[test]
+    fn rejects_empty_dropdowns() {
+        let fields = [BlockingFormDialogField {
+            id: "size",
+            label: "Size",
+            kind: BlockingFormDialogFieldKind::Dropdown(&[]),
+        }];
+
+        let err = BlockingFormDialog::new(NoParent)
+            .fields(&fields)
+            .show()
+            .unwrap_err();
+        assert!(
+            matches!(err, BlockingDialogError::Io(err) if err.kind() == io::ErrorKind::InvalidInput)
+        );
+        assert!(take_requests().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "no mock response queued for save file dialog")]
+    fn panics_without_a_response() {
+        let _ = BlockingSaveFileDialog::new(NoParent).show();
+    }
+
+    #[test]
+    #[should_panic(expected = "can't answer a confirm dialog")]
+    fn panics_on_the_wrong_response() {
+        push(Response::Rows(vec![0]));
+        let _ = BlockingConfirmDialog::new(NoParent).show();
+    }
+}
@@ -0,0 +1,89 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// How often dialogs pick up changes made by the worker
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// What a progress dialog shows, as last set by the worker
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ProgressState {
+    /// Between 0 and 1, `None` while pulsing
+    pub fraction: Option<f64>,
+    /// Replaces the dialog's message once set
+    pub text: Option<String>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<ProgressState>,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// Passed to the worker of a progress dialog, to report progress and
+/// check whether the user cancelled.
+#[derive(Debug, Clone)]
+pub struct Progress(Arc<Shared>);
+
+impl Progress {
+    pub(crate) fn new() -> Self {
+        Self(Arc::new(Shared {
+            state: Mutex::new(ProgressState {
+                fraction: Some(0.0),
+                text: None,
+            }),
+            cancelled: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+        }))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ProgressState> {
+        // The state is always consistent, even if a worker panicked holding it
+        self.0.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Sets how much is done, from 0 to 1, and stops pulsing
+    pub fn set_fraction(&self, fraction: f64) {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        self.lock().fraction = Some(fraction);
+    }
+
+    /// Replaces the message shown above the bar
+    pub fn set_text(&self, text: &str) {
+        self.lock().text = Some(text.to_string());
+    }
+
+    /// Shows activity of unknown length until the next `set_fraction`
+    pub fn pulse(&self) {
+        self.lock().fraction = None;
+    }
+
+    /// Whether the user cancelled, the worker should return soon after
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn get_state(&self) -> ProgressState {
+        self.lock().clone()
+    }
+
+    pub(crate) fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn finish(&self) {
+        self.0.finished.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.0.finished.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{Ordering, compiler_fence};
+
+/// A password entered in a dialog, wiped from memory when dropped.
+///
+/// `Debug` never shows the contents, read them with [`Secret::expose`].
+pub struct Secret(String);
+
+impl Secret {
+    pub(crate) fn new(secret: String) -> Self {
+        Self(secret)
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        // Zero bytes are valid UTF-8, so the string stays well-formed
+        zeroize(unsafe { self.0.as_mut_vec() });
+    }
+}
+
+/// Overwrites the whole allocation, in a way the compiler can't optimize out
+pub(crate) fn zeroize<T: Copy + Default>(values: &mut Vec<T>) {
+    for value in values.iter_mut() {
+        unsafe { ptr::write_volatile(value, T::default()) };
+    }
+
+    for value in values.spare_capacity_mut() {
+        unsafe { ptr::write_volatile(value, MaybeUninit::new(T::default())) };
+    }
+
+    compiler_fence(Ordering::SeqCst);
+}
@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::widen;
+use std::cell::RefCell;
+use std::ffi::c_void;
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, GetClassNameW, HCBT_ACTIVATE, HHOOK, MESSAGEBOX_RESULT, SetDlgItemTextW,
+    SetWindowsHookExW, UnhookWindowsHookEx, WH_CBT,
+};
+use windows::core::PCWSTR;
+
+/// Window class of message boxes and other dialogs
+const DIALOG_CLASS: &str = "#32770";
+
+thread_local! {
+    static LABELS: RefCell<Vec<(MESSAGEBOX_RESULT, Vec<u16>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Renames the buttons of the next message box shown on this thread, until
+/// dropped. Task dialogs would do this natively but aren't available on XP.
+pub struct ButtonLabels {
+    hook: Option<HHOOK>,
+}
+
+impl ButtonLabels {
+    /// Buttons are identified by the id `MessageBoxW` returns for them,
+    /// those without a label keep the localized default
+    pub fn install(labels: &[(MESSAGEBOX_RESULT, Option<&str>)]) -> Self {
+        let labels = labels
+            .iter()
+            .filter_map(|(id, label)| label.map(|label| (*id, widen(label))))
+            .collect::<Vec<_>>();
+
+        if labels.is_empty() {
+            return ButtonLabels { hook: None };
+        }
+
+        LABELS.with_borrow_mut(|current| *current = labels);
+
+        // If the hook can't be installed the default labels are shown
+        let hook = unsafe { SetWindowsHookExW(WH_CBT, Some(cbt_proc), None, GetCurrentThreadId()) };
+
+        ButtonLabels { hook: hook.ok() }
+    }
+}
+
+impl Drop for ButtonLabels {
+    fn drop(&mut self) {
+        if let Some(hook) = self.hook.take() {
+            let _ = unsafe { UnhookWindowsHookEx(hook) };
+        }
+
+        LABELS.with_borrow_mut(Vec::clear);
+    }
+}
+
+fn is_dialog(hwnd: HWND) -> bool {
+    let mut class = [0u16; 16];
+    let len = unsafe { GetClassNameW(hwnd, &mut class) };
+
+    String::from_utf16_lossy(&class[..len.max(0) as usize]) == DIALOG_CLASS
+}
+
+unsafe extern "system" fn cbt_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    let hwnd = HWND(wparam.0 as *mut c_void);
+
+    // Other windows, like the parent, can be activated before the message box
+    if code == HCBT_ACTIVATE as i32 && is_dialog(hwnd) {
+        // Taken so that dialogs opened later from the same thread keep their labels
+        for (id, label) in LABELS.take() {
+            let _ = unsafe { SetDlgItemTextW(hwnd, id.0, PCWSTR(label.as_ptr())) };
+        }
+    }
+
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
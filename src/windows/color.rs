@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::get_hwnd;
+use crate::{BlockingColorDialog, BlockingDialogError, Rgba};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::cell::Cell;
+use windows::Win32::Foundation::COLORREF;
+use windows::Win32::UI::Controls::Dialogs::{
+    CC_ANYCOLOR, CC_FULLOPEN, CC_RGBINIT, CHOOSECOLORW, ChooseColorW,
+};
+
+thread_local! {
+    /// The dialog's custom colors, kept between calls like other apps do
+    static CUSTOM_COLORS: Cell<[COLORREF; 16]> = const { Cell::new([COLORREF(0x00ff_ffff); 16]) };
+}
+
+/// A `COLORREF` is laid out as 0x00bbggrr
+fn get_colorref(color: Rgba) -> COLORREF {
+    COLORREF(u32::from_le_bytes([color.r, color.g, color.b, 0]))
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingColorDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Rgba>, BlockingDialogError> {
+        let hwnd = get_hwnd(&self.window)?;
+
+        // ChooseColorW has neither a title nor transparency, so both are ignored
+        let mut custom_colors = CUSTOM_COLORS.get();
+
+        let mut flags = CC_ANYCOLOR | CC_FULLOPEN;
+        if self.initial.is_some() {
+            flags |= CC_RGBINIT;
+        }
+
+        let mut cc = CHOOSECOLORW {
+            lStructSize: std::mem::size_of::<CHOOSECOLORW>() as u32,
+            hwndOwner: hwnd.unwrap_or_default(),
+            rgbResult: get_colorref(self.initial.unwrap_or_default()),
+            lpCustColors: custom_colors.as_mut_ptr(),
+            Flags: flags,
+            ..Default::default()
+        };
+
+        let result = unsafe { ChooseColorW(&mut cc).as_bool() };
+        CUSTOM_COLORS.set(custom_colors);
+
+        if !result {
+            return Ok(None);
+        }
+
+        let [r, g, b, _] = cc.rgbResult.0.to_le_bytes();
+        Ok(Some(Rgba::rgb(r, g, b)))
+    }
+}
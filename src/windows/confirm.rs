@@ -1,46 +1,33 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use super::widen;
+use super::button_labels::ButtonLabels;
+use super::{get_hwnd, get_icon_style, widen};
 use crate::{BlockingConfirmDialog, BlockingDialogError, BlockingDialogLevel};
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
-use std::ffi::c_void;
-use windows::Win32::Foundation::HWND;
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use windows::Win32::UI::WindowsAndMessaging::{
-    MB_ICONERROR, MB_ICONINFORMATION, MB_ICONWARNING, MB_OKCANCEL, MB_SETFOREGROUND, MB_TOPMOST,
-    MESSAGEBOX_RESULT, MESSAGEBOX_STYLE, MessageBoxW,
+    IDCANCEL, IDOK, MB_OKCANCEL, MB_SETFOREGROUND, MB_TOPMOST, MESSAGEBOX_RESULT, MESSAGEBOX_STYLE,
+    MessageBoxW,
 };
 use windows::core::PCWSTR;
 
 fn get_utype(level: BlockingDialogLevel) -> MESSAGEBOX_STYLE {
-    let level = match level {
-        BlockingDialogLevel::Info => MB_ICONINFORMATION,
-        BlockingDialogLevel::Warning => MB_ICONWARNING,
-        BlockingDialogLevel::Error => MB_ICONERROR,
-    };
-
-    level | MB_OKCANCEL
+    get_icon_style(level) | MB_OKCANCEL
 }
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingConfirmDialog<'a, W> {
-    pub fn show(&self) -> Result<bool, BlockingDialogError> {
+    pub(crate) fn show_native(&self) -> Result<bool, BlockingDialogError> {
         let title_wide = widen(self.title);
         let message_wide = widen(self.message);
 
-        let w = self
-            .window
-            .window_handle()
-            .map_err(BlockingDialogError::Handle)?;
-
-        let RawWindowHandle::Win32(handle) = w.as_raw() else {
-            return Err(BlockingDialogError::Handle(HandleError::NotSupported));
-        };
+        let hwnd = get_hwnd(&self.window)?;
 
-        let hwnd = HWND(handle.hwnd.get() as *mut c_void);
+        let _labels =
+            ButtonLabels::install(&[(IDOK, self.accept_label), (IDCANCEL, self.cancel_label)]);
 
         let yes = unsafe {
             let res = MessageBoxW(
-                Some(hwnd),
+                hwnd,
                 PCWSTR(message_wide.as_ptr()),
                 PCWSTR(title_wide.as_ptr()),
                 get_utype(self.level),
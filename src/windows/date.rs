@@ -0,0 +1,141 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::get_hwnd;
+use super::template::{
+    CONTROL_HEIGHT, DATE_PICKER_CLASS, MARGIN, SS_NOPREFIX, STATIC_CLASS, TEXT_WIDTH, Template,
+    init_common_controls, text_height,
+};
+use crate::{BlockingDateDialog, BlockingDialogError, Date};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::cell::Cell;
+use windows::Win32::Foundation::{HWND, LPARAM, SYSTEMTIME, WPARAM};
+use windows::Win32::System::SystemInformation::GetLocalTime;
+use windows::Win32::UI::Controls::{
+    DTM_GETSYSTEMTIME, DTM_SETRANGE, DTM_SETSYSTEMTIME, DTS_SHORTDATEFORMAT, GDT_VALID, GDTR_MAX,
+    GDTR_MIN, ICC_DATE_CLASSES,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    EndDialog, IDCANCEL, IDOK, SendDlgItemMessageW, WM_COMMAND, WM_INITDIALOG, WS_TABSTOP,
+};
+
+const MESSAGE_ID: u16 = 10;
+const DATE_PICKER_ID: u16 = 11;
+
+thread_local! {
+    /// Initial date and bounds of the dialog being shown, and the date once OK is clicked
+    static DATES: Cell<(Date, Option<Date>, Option<Date>)> =
+        const { Cell::new((Date::new(1970, 1, 1), None, None)) };
+    static PICKED: Cell<Option<Date>> = const { Cell::new(None) };
+}
+
+fn get_system_time(date: Date) -> SYSTEMTIME {
+    SYSTEMTIME {
+        wYear: date.year.clamp(1601, 30827) as u16,
+        wMonth: date.month as u16,
+        wDay: date.day as u16,
+        ..Default::default()
+    }
+}
+
+unsafe extern "system" fn dialog_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    _lparam: LPARAM,
+) -> isize {
+    let send = |msg, wparam, lparam| unsafe {
+        SendDlgItemMessageW(
+            hwnd,
+            DATE_PICKER_ID as i32,
+            msg,
+            WPARAM(wparam),
+            LPARAM(lparam),
+        )
+    };
+
+    match msg {
+        WM_INITDIALOG => {
+            let (initial, min, max) = DATES.get();
+
+            let mut flags = 0;
+            if min.is_some() {
+                flags |= GDTR_MIN;
+            }
+            if max.is_some() {
+                flags |= GDTR_MAX;
+            }
+
+            let range = [min, max].map(|date| date.map(get_system_time).unwrap_or_default());
+            send(DTM_SETRANGE, flags as usize, range.as_ptr() as isize);
+
+            let time = get_system_time(initial);
+            send(
+                DTM_SETSYSTEMTIME,
+                GDT_VALID.0 as usize,
+                &time as *const SYSTEMTIME as isize,
+            );
+
+            // Let the dialog focus the date picker
+            1
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xffff) as i32;
+
+            if id == IDOK.0 {
+                let mut time = SYSTEMTIME::default();
+                send(DTM_GETSYSTEMTIME, 0, &mut time as *mut SYSTEMTIME as isize);
+
+                let date = Date::new(time.wYear as i32, time.wMonth as u32, time.wDay as u32);
+                PICKED.set(Some(date));
+                let _ = unsafe { EndDialog(hwnd, 1) };
+            } else if id == IDCANCEL.0 {
+                let _ = unsafe { EndDialog(hwnd, 0) };
+            }
+
+            1
+        }
+        _ => 0,
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingDateDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Date>, BlockingDialogError> {
+        let hwnd = get_hwnd(&self.window)?;
+
+        init_common_controls(ICC_DATE_CLASSES);
+
+        let text_height = text_height(self.message, TEXT_WIDTH);
+        let picker_y = MARGIN + text_height + MARGIN;
+
+        let width = TEXT_WIDTH + 2 * MARGIN;
+        let button_y = picker_y + CONTROL_HEIGHT + 2 * MARGIN;
+        let height = button_y + CONTROL_HEIGHT + MARGIN;
+
+        let mut template = Template::default();
+        let text_rect = (MARGIN, MARGIN, TEXT_WIDTH, text_height);
+        template.item(
+            STATIC_CLASS,
+            MESSAGE_ID,
+            SS_NOPREFIX,
+            text_rect,
+            self.message,
+        );
+        let picker_rect = (MARGIN, picker_y, TEXT_WIDTH, CONTROL_HEIGHT);
+        let style = WS_TABSTOP.0 | DTS_SHORTDATEFORMAT;
+        template.named_item(DATE_PICKER_CLASS, DATE_PICKER_ID, style, picker_rect, "");
+
+        let buttons = [(IDOK.0 as u16, "OK"), (IDCANCEL.0 as u16, "Cancel")];
+        template.buttons(&buttons, IDOK.0 as u16, width - MARGIN, button_y);
+
+        let today = unsafe { GetLocalTime() };
+        let today = Date::new(today.wYear as i32, today.wMonth as u32, today.wDay as u32);
+        let initial = self.clamp(self.initial.unwrap_or(today));
+        DATES.set((initial, self.min, self.max));
+        PICKED.set(None);
+
+        template.show(self.title, width, height, hwnd, Some(dialog_proc))?;
+
+        Ok(PICKED.take())
+    }
+}
@@ -0,0 +1,241 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::template::{
+    BUTTON_CLASS, COMBOBOX_CLASS, CONTROL_HEIGHT, DATE_PICKER_CLASS, EDIT_CLASS, LINE_HEIGHT,
+    MARGIN, SS_NOPREFIX, STATIC_CLASS, TEXT_WIDTH, Template, get_item_secret, get_item_text,
+    init_common_controls, text_height,
+};
+use super::{get_hwnd, widen};
+use crate::{
+    BlockingDialogError, BlockingFormDialog, BlockingFormDialogFieldKind, BlockingFormDialogValue,
+};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use windows::Win32::Foundation::{HWND, LPARAM, SYSTEMTIME, WPARAM};
+use windows::Win32::UI::Controls::{
+    BST_CHECKED, DTM_GETSYSTEMTIME, DTS_SHORTDATEFORMAT, ICC_DATE_CLASSES,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    BM_GETCHECK, BS_AUTOCHECKBOX, CB_ADDSTRING, CB_GETCURSEL, CB_SETCURSEL, CBS_DROPDOWNLIST,
+    ES_AUTOHSCROLL, ES_AUTOVSCROLL, ES_MULTILINE, ES_PASSWORD, ES_WANTRETURN, EndDialog, IDCANCEL,
+    IDOK, SendDlgItemMessageW, WM_COMMAND, WM_INITDIALOG, WS_BORDER, WS_TABSTOP, WS_VSCROLL,
+};
+
+const MESSAGE_ID: u16 = 10;
+/// Each field gets a label id from here, followed by its control id
+const FIRST_FIELD_ID: u16 = 100;
+
+const LABEL_WIDTH: i32 = 70;
+const MULTILINE_HEIGHT: i32 = 3 * CONTROL_HEIGHT;
+/// Height of a dropdown's list when opened
+const DROPDOWN_HEIGHT: i32 = 8 * LINE_HEIGHT;
+
+/// What the dialog procedure needs to know about each field
+enum Field {
+    Text,
+    Password,
+    Dropdown(Vec<Vec<u16>>),
+    Checkbox,
+    Date,
+}
+
+thread_local! {
+    /// The fields of the form being shown, and their values once OK is clicked
+    static FIELDS: RefCell<Vec<Field>> = const { RefCell::new(Vec::new()) };
+    static VALUES: RefCell<Option<Vec<BlockingFormDialogValue>>> = const { RefCell::new(None) };
+}
+
+fn get_control_id(index: usize) -> u16 {
+    FIRST_FIELD_ID + 2 * index as u16 + 1
+}
+
+fn get_value(hwnd: HWND, id: u16, field: &Field) -> BlockingFormDialogValue {
+    let send = |msg, lparam| unsafe {
+        SendDlgItemMessageW(hwnd, id as i32, msg, WPARAM(0), LPARAM(lparam)).0
+    };
+
+    match field {
+        Field::Text => {
+            let text = get_item_text(hwnd, id).replace("\r\n", "\n");
+            BlockingFormDialogValue::Text(text)
+        }
+        Field::Password => BlockingFormDialogValue::Password(get_item_secret(hwnd, id)),
+        Field::Dropdown(_) => {
+            BlockingFormDialogValue::Dropdown(send(CB_GETCURSEL, 0).max(0) as usize)
+        }
+        Field::Checkbox => {
+            BlockingFormDialogValue::Checkbox(send(BM_GETCHECK, 0) == BST_CHECKED.0 as isize)
+        }
+        Field::Date => {
+            let mut time = SYSTEMTIME::default();
+            send(DTM_GETSYSTEMTIME, &mut time as *mut SYSTEMTIME as isize);
+
+            BlockingFormDialogValue::Date(time.wYear as i32, time.wMonth as u32, time.wDay as u32)
+        }
+    }
+}
+
+unsafe extern "system" fn dialog_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    _lparam: LPARAM,
+) -> isize {
+    match msg {
+        WM_INITDIALOG => {
+            FIELDS.with_borrow(|fields| {
+                for (i, field) in fields.iter().enumerate() {
+                    let Field::Dropdown(options) = field else {
+                        continue;
+                    };
+
+                    let id = get_control_id(i) as i32;
+
+                    unsafe {
+                        for option in options {
+                            let option = LPARAM(option.as_ptr() as isize);
+                            SendDlgItemMessageW(hwnd, id, CB_ADDSTRING, WPARAM(0), option);
+                        }
+
+                        SendDlgItemMessageW(hwnd, id, CB_SETCURSEL, WPARAM(0), LPARAM(0));
+                    }
+                }
+            });
+
+            // Let the dialog focus the first field
+            1
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xffff) as i32;
+
+            if id == IDOK.0 {
+                let values = FIELDS.with_borrow(|fields| {
+                    fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, field)| get_value(hwnd, get_control_id(i), field))
+                        .collect()
+                });
+
+                VALUES.set(Some(values));
+                let _ = unsafe { EndDialog(hwnd, 1) };
+            } else if id == IDCANCEL.0 {
+                let _ = unsafe { EndDialog(hwnd, 0) };
+            }
+
+            1
+        }
+        _ => 0,
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingFormDialog<'a, W> {
+    pub(crate) fn show_native(
+        &self,
+    ) -> Result<Option<HashMap<String, BlockingFormDialogValue>>, BlockingDialogError> {
+        let hwnd = get_hwnd(&self.window)?;
+
+        init_common_controls(ICC_DATE_CLASSES);
+
+        let text_height = text_height(self.message, TEXT_WIDTH);
+        let control_x = MARGIN + LABEL_WIDTH;
+        let control_width = TEXT_WIDTH - LABEL_WIDTH;
+        // Labels are centered on the first line of their control
+        let label_offset = (CONTROL_HEIGHT - LINE_HEIGHT) / 2;
+
+        let mut template = Template::default();
+        let text_rect = (MARGIN, MARGIN, TEXT_WIDTH, text_height);
+        template.item(
+            STATIC_CLASS,
+            MESSAGE_ID,
+            SS_NOPREFIX,
+            text_rect,
+            self.message,
+        );
+
+        let mut fields = Vec::new();
+        let mut y = MARGIN + text_height + MARGIN;
+
+        for (i, field) in self.fields.iter().enumerate() {
+            let label_id = get_control_id(i) - 1;
+            let id = get_control_id(i);
+            let edit_style = WS_BORDER.0 | WS_TABSTOP.0 | ES_AUTOHSCROLL as u32;
+            let rect = (control_x, y, control_width, CONTROL_HEIGHT);
+
+            // Checkboxes carry their own label
+            if !matches!(field.kind, BlockingFormDialogFieldKind::Checkbox) {
+                let label_rect = (MARGIN, y + label_offset, LABEL_WIDTH, LINE_HEIGHT);
+                template.item(STATIC_CLASS, label_id, 0, label_rect, field.label);
+            }
+
+            let height = match field.kind {
+                BlockingFormDialogFieldKind::Text => {
+                    template.item(EDIT_CLASS, id, edit_style, rect, "");
+                    fields.push(Field::Text);
+                    CONTROL_HEIGHT
+                }
+                BlockingFormDialogFieldKind::Password => {
+                    let style = edit_style | ES_PASSWORD as u32;
+                    template.item(EDIT_CLASS, id, style, rect, "");
+                    fields.push(Field::Password);
+                    CONTROL_HEIGHT
+                }
+                BlockingFormDialogFieldKind::Multiline => {
+                    let style = WS_BORDER.0
+                        | WS_TABSTOP.0
+                        | WS_VSCROLL.0
+                        | (ES_MULTILINE | ES_WANTRETURN | ES_AUTOVSCROLL) as u32;
+                    let rect = (control_x, y, control_width, MULTILINE_HEIGHT);
+                    template.item(EDIT_CLASS, id, style, rect, "");
+                    fields.push(Field::Text);
+                    MULTILINE_HEIGHT
+                }
+                BlockingFormDialogFieldKind::Dropdown(options) => {
+                    let style = WS_TABSTOP.0 | WS_VSCROLL.0 | CBS_DROPDOWNLIST as u32;
+                    let rect = (control_x, y, control_width, DROPDOWN_HEIGHT);
+                    template.item(COMBOBOX_CLASS, id, style, rect, "");
+                    fields.push(Field::Dropdown(options.iter().map(widen).collect()));
+                    CONTROL_HEIGHT
+                }
+                BlockingFormDialogFieldKind::Checkbox => {
+                    let style = WS_TABSTOP.0 | BS_AUTOCHECKBOX as u32;
+                    template.item(BUTTON_CLASS, id, style, rect, field.label);
+                    fields.push(Field::Checkbox);
+                    CONTROL_HEIGHT
+                }
+                BlockingFormDialogFieldKind::Date => {
+                    let style = WS_TABSTOP.0 | DTS_SHORTDATEFORMAT;
+                    template.named_item(DATE_PICKER_CLASS, id, style, rect, "");
+                    fields.push(Field::Date);
+                    CONTROL_HEIGHT
+                }
+            };
+
+            y += height + MARGIN;
+        }
+
+        let width = TEXT_WIDTH + 2 * MARGIN;
+        let button_y = y + MARGIN;
+        let height = button_y + CONTROL_HEIGHT + MARGIN;
+
+        let buttons = [(IDOK.0 as u16, "OK"), (IDCANCEL.0 as u16, "Cancel")];
+        template.buttons(&buttons, IDOK.0 as u16, width - MARGIN, button_y);
+
+        FIELDS.set(fields);
+        VALUES.set(None);
+
+        template.show(self.title, width, height, hwnd, Some(dialog_proc))?;
+
+        let values = VALUES.take().map(|values| {
+            self.fields
+                .iter()
+                .zip(values)
+                .map(|(field, value)| (field.id.to_string(), value))
+                .collect()
+        });
+
+        Ok(values)
+    }
+}
@@ -0,0 +1,131 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::template::{
+    CONTROL_HEIGHT, EDIT_CLASS, MARGIN, SS_NOPREFIX, STATIC_CLASS, TEXT_WIDTH, Template,
+    get_item_secret, text_height,
+};
+use super::{get_hwnd, widen};
+use crate::{BlockingDialogError, BlockingInputDialog, Secret};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::cell::RefCell;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::Controls::{EM_SETCUEBANNER, EM_SETSEL};
+use windows::Win32::UI::WindowsAndMessaging::{
+    ES_AUTOHSCROLL, EndDialog, GetDlgItem, IDCANCEL, IDOK, SendDlgItemMessageW, SendMessageW,
+    WM_COMMAND, WM_INITDIALOG, WM_NEXTDLGCTL, WS_BORDER, WS_TABSTOP,
+};
+
+const MESSAGE_ID: u16 = 10;
+const EDIT_ID: u16 = 11;
+
+thread_local! {
+    /// Cue banner of the entry, and its text once OK is clicked, read as a
+    /// secret in case it's a password
+    static PLACEHOLDER: RefCell<Vec<u16>> = const { RefCell::new(Vec::new()) };
+    static TEXT: RefCell<Option<Secret>> = const { RefCell::new(None) };
+}
+
+unsafe extern "system" fn dialog_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    _lparam: LPARAM,
+) -> isize {
+    match msg {
+        WM_INITDIALOG => {
+            unsafe {
+                PLACEHOLDER.with_borrow(|placeholder| {
+                    if placeholder.len() > 1 {
+                        SendDlgItemMessageW(
+                            hwnd,
+                            EDIT_ID as i32,
+                            EM_SETCUEBANNER,
+                            WPARAM(0),
+                            LPARAM(placeholder.as_ptr() as isize),
+                        );
+                    }
+                });
+
+                // Select the default text so typing replaces it
+                SendDlgItemMessageW(hwnd, EDIT_ID as i32, EM_SETSEL, WPARAM(0), LPARAM(-1));
+
+                if let Ok(edit) = GetDlgItem(Some(hwnd), EDIT_ID as i32) {
+                    SendMessageW(
+                        hwnd,
+                        WM_NEXTDLGCTL,
+                        Some(WPARAM(edit.0 as usize)),
+                        Some(LPARAM(1)),
+                    );
+                }
+            }
+
+            // Focus was already moved to the entry
+            0
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xffff) as i32;
+
+            if id == IDOK.0 {
+                TEXT.set(Some(get_item_secret(hwnd, EDIT_ID)));
+                let _ = unsafe { EndDialog(hwnd, 1) };
+            } else if id == IDCANCEL.0 {
+                let _ = unsafe { EndDialog(hwnd, 0) };
+            }
+
+            1
+        }
+        _ => 0,
+    }
+}
+
+/// Shows a message above a single-line entry, with `style` added to the entry's own
+pub fn show_entry(
+    hwnd: Option<HWND>,
+    title: &str,
+    message: &str,
+    default_text: &str,
+    placeholder: &str,
+    style: u32,
+) -> Result<Option<Secret>, BlockingDialogError> {
+    let text_height = text_height(message, TEXT_WIDTH);
+    let edit_y = MARGIN + text_height + MARGIN;
+    let button_y = edit_y + CONTROL_HEIGHT + 2 * MARGIN;
+
+    let width = TEXT_WIDTH + 2 * MARGIN;
+    let height = button_y + CONTROL_HEIGHT + MARGIN;
+
+    let mut template = Template::default();
+    let text_rect = (MARGIN, MARGIN, TEXT_WIDTH, text_height);
+    template.item(STATIC_CLASS, MESSAGE_ID, SS_NOPREFIX, text_rect, message);
+    let edit_style = style | WS_BORDER.0 | WS_TABSTOP.0 | ES_AUTOHSCROLL as u32;
+    let edit_rect = (MARGIN, edit_y, TEXT_WIDTH, CONTROL_HEIGHT);
+    template.item(EDIT_CLASS, EDIT_ID, edit_style, edit_rect, default_text);
+
+    let buttons = [(IDOK.0 as u16, "OK"), (IDCANCEL.0 as u16, "Cancel")];
+    template.buttons(&buttons, IDOK.0 as u16, width - MARGIN, button_y);
+
+    PLACEHOLDER.set(widen(placeholder));
+    TEXT.set(None);
+
+    template.show(title, width, height, hwnd, Some(dialog_proc))?;
+
+    Ok(TEXT.take())
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingInputDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<String>, BlockingDialogError> {
+        let hwnd = get_hwnd(&self.window)?;
+
+        let text = show_entry(
+            hwnd,
+            self.title,
+            self.message,
+            self.default_text.unwrap_or_default(),
+            self.placeholder.unwrap_or_default(),
+            0,
+        )?;
+
+        Ok(text.map(|text| text.expose().to_string()))
+    }
+}
@@ -0,0 +1,264 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::template::{
+    CONTROL_HEIGHT, LINE_HEIGHT, LIST_VIEW_CLASS, MARGIN, SS_NOPREFIX, STATIC_CLASS, TEXT_WIDTH,
+    Template, init_common_controls, text_height,
+};
+use super::{get_hwnd, widen};
+use crate::{BlockingDialogError, BlockingListDialog, BlockingListDialogMode};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::cell::RefCell;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::Controls::{
+    ICC_LISTVIEW_CLASSES, LIST_VIEW_ITEM_STATE_FLAGS, LVCF_TEXT, LVCOLUMNW, LVIF_TEXT,
+    LVIS_FOCUSED, LVIS_SELECTED, LVIS_STATEIMAGEMASK, LVITEMW, LVM_ENSUREVISIBLE, LVM_GETITEMSTATE,
+    LVM_GETNEXTITEM, LVM_INSERTCOLUMNW, LVM_INSERTITEMW, LVM_SETCOLUMNWIDTH,
+    LVM_SETEXTENDEDLISTVIEWSTYLE, LVM_SETITEMSTATE, LVM_SETITEMTEXTW, LVNI_SELECTED,
+    LVS_EX_CHECKBOXES, LVS_EX_FULLROWSELECT, LVS_NOCOLUMNHEADER, LVS_REPORT, LVS_SHOWSELALWAYS,
+    LVS_SINGLESEL, LVSCW_AUTOSIZE_USEHEADER,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    EndDialog, IDCANCEL, IDOK, SendDlgItemMessageW, WM_COMMAND, WM_INITDIALOG, WS_BORDER,
+    WS_TABSTOP,
+};
+use windows::core::PWSTR;
+
+const MESSAGE_ID: u16 = 10;
+const LIST_ID: u16 = 11;
+
+const LIST_HEIGHT: i32 = 15 * LINE_HEIGHT;
+
+/// State images of a checkbox list view, shifted into `LVIS_STATEIMAGEMASK`
+const UNCHECKED: u32 = 1 << 12;
+const CHECKED: u32 = 2 << 12;
+
+/// What the dialog procedure needs to fill in the list view
+struct List {
+    columns: Vec<Vec<u16>>,
+    rows: Vec<Vec<Vec<u16>>>,
+    selected: Vec<usize>,
+    checkboxes: bool,
+}
+
+thread_local! {
+    /// The list being shown, and the selected rows once OK is clicked
+    static LIST: RefCell<Option<List>> = const { RefCell::new(None) };
+    static SELECTED: RefCell<Option<Vec<usize>>> = const { RefCell::new(None) };
+}
+
+fn send(hwnd: HWND, msg: u32, wparam: usize, lparam: isize) -> isize {
+    unsafe { SendDlgItemMessageW(hwnd, LIST_ID as i32, msg, WPARAM(wparam), LPARAM(lparam)).0 }
+}
+
+fn set_item_state(hwnd: HWND, index: usize, state: u32, mask: u32) {
+    let item = LVITEMW {
+        state: LIST_VIEW_ITEM_STATE_FLAGS(state),
+        stateMask: LIST_VIEW_ITEM_STATE_FLAGS(mask),
+        ..Default::default()
+    };
+
+    send(
+        hwnd,
+        LVM_SETITEMSTATE,
+        index,
+        &item as *const LVITEMW as isize,
+    );
+}
+
+fn fill(hwnd: HWND, list: &List) {
+    if list.checkboxes {
+        let style = (LVS_EX_FULLROWSELECT | LVS_EX_CHECKBOXES) as isize;
+        send(hwnd, LVM_SETEXTENDEDLISTVIEWSTYLE, 0, style);
+    } else {
+        let style = LVS_EX_FULLROWSELECT as isize;
+        send(hwnd, LVM_SETEXTENDEDLISTVIEWSTYLE, 0, style);
+    }
+
+    // The list view only reads the text, it isn't written through these pointers
+    for (i, column) in list.columns.iter().enumerate() {
+        let column = LVCOLUMNW {
+            mask: LVCF_TEXT,
+            pszText: PWSTR(column.as_ptr() as *mut u16),
+            ..Default::default()
+        };
+
+        send(
+            hwnd,
+            LVM_INSERTCOLUMNW,
+            i,
+            &column as *const LVCOLUMNW as isize,
+        );
+    }
+
+    for (i, row) in list.rows.iter().enumerate() {
+        for (j, cell) in row.iter().enumerate() {
+            let item = LVITEMW {
+                mask: LVIF_TEXT,
+                iItem: i as i32,
+                iSubItem: j as i32,
+                pszText: PWSTR(cell.as_ptr() as *mut u16),
+                ..Default::default()
+            };
+            let lparam = &item as *const LVITEMW as isize;
+
+            if j == 0 {
+                send(hwnd, LVM_INSERTITEMW, 0, lparam);
+            } else {
+                send(hwnd, LVM_SETITEMTEXTW, i, lparam);
+            }
+        }
+    }
+
+    for i in 0..list.columns.len() {
+        send(
+            hwnd,
+            LVM_SETCOLUMNWIDTH,
+            i,
+            LVSCW_AUTOSIZE_USEHEADER as isize,
+        );
+    }
+
+    for i in 0..list.rows.len() {
+        let selected = list.selected.contains(&i);
+
+        if list.checkboxes {
+            let state = if selected { CHECKED } else { UNCHECKED };
+            set_item_state(hwnd, i, state, LVIS_STATEIMAGEMASK.0);
+        } else if selected {
+            let state = LVIS_SELECTED.0 | LVIS_FOCUSED.0;
+            set_item_state(hwnd, i, state, state);
+            send(hwnd, LVM_ENSUREVISIBLE, i, 0);
+        }
+    }
+}
+
+fn get_selected(hwnd: HWND, list: &List) -> Vec<usize> {
+    if list.checkboxes {
+        return (0..list.rows.len())
+            .filter(|i| {
+                let state = send(hwnd, LVM_GETITEMSTATE, *i, LVIS_STATEIMAGEMASK.0 as isize);
+                state as u32 == CHECKED
+            })
+            .collect();
+    }
+
+    let index = send(hwnd, LVM_GETNEXTITEM, usize::MAX, LVNI_SELECTED as isize);
+    usize::try_from(index).into_iter().collect()
+}
+
+unsafe extern "system" fn dialog_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    _lparam: LPARAM,
+) -> isize {
+    match msg {
+        WM_INITDIALOG => {
+            LIST.with_borrow(|list| {
+                if let Some(list) = list {
+                    fill(hwnd, list);
+                }
+            });
+
+            // Let the dialog focus the list
+            1
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xffff) as i32;
+
+            if id == IDOK.0 {
+                let selected = LIST.with_borrow(|list| {
+                    list.as_ref()
+                        .map(|list| get_selected(hwnd, list))
+                        .unwrap_or_default()
+                });
+
+                SELECTED.set(Some(selected));
+                let _ = unsafe { EndDialog(hwnd, 1) };
+            } else if id == IDCANCEL.0 {
+                let _ = unsafe { EndDialog(hwnd, 0) };
+            }
+
+            1
+        }
+        _ => 0,
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingListDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Vec<usize>, BlockingDialogError> {
+        let hwnd = get_hwnd(&self.window)?;
+
+        init_common_controls(ICC_LISTVIEW_CLASSES);
+
+        // Every row gets a cell in every column, even without headers
+        let width = self
+            .rows
+            .iter()
+            .map(|row| row.len())
+            .chain([self.columns.len(), 1])
+            .max()
+            .unwrap_or(1);
+
+        let columns = (0..width)
+            .map(|i| widen(self.columns.get(i).copied().unwrap_or_default()))
+            .collect();
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                (0..width)
+                    .map(|i| widen(row.get(i).copied().unwrap_or_default()))
+                    .collect()
+            })
+            .collect();
+
+        let checkboxes = self.mode == BlockingListDialogMode::Checklist;
+
+        let text_height = text_height(self.message, TEXT_WIDTH);
+
+        let mut template = Template::default();
+        let text_rect = (MARGIN, MARGIN, TEXT_WIDTH, text_height);
+        template.item(
+            STATIC_CLASS,
+            MESSAGE_ID,
+            SS_NOPREFIX,
+            text_rect,
+            self.message,
+        );
+
+        let mut style = WS_BORDER.0 | WS_TABSTOP.0 | LVS_REPORT | LVS_SHOWSELALWAYS;
+        if !checkboxes {
+            style |= LVS_SINGLESEL;
+        }
+        if self.columns.is_empty() {
+            style |= LVS_NOCOLUMNHEADER;
+        }
+
+        let list_y = MARGIN + text_height + MARGIN;
+        let list_rect = (MARGIN, list_y, TEXT_WIDTH, LIST_HEIGHT);
+        template.named_item(LIST_VIEW_CLASS, LIST_ID, style, list_rect, "");
+
+        let width = TEXT_WIDTH + 2 * MARGIN;
+        let button_y = list_y + LIST_HEIGHT + 2 * MARGIN;
+        let height = button_y + CONTROL_HEIGHT + MARGIN;
+
+        let buttons = [(IDOK.0 as u16, "OK"), (IDCANCEL.0 as u16, "Cancel")];
+        template.buttons(&buttons, IDOK.0 as u16, width - MARGIN, button_y);
+
+        LIST.set(Some(List {
+            columns,
+            rows,
+            selected: self.get_selected(),
+            checkboxes,
+        }));
+        SELECTED.set(None);
+
+        let res = template.show(self.title, width, height, hwnd, Some(dialog_proc));
+        LIST.set(None);
+        res?;
+
+        Ok(SELECTED.take().unwrap_or_default())
+    }
+}
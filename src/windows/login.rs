@@ -0,0 +1,127 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::get_hwnd;
+use super::template::{
+    CONTROL_HEIGHT, EDIT_CLASS, LINE_HEIGHT, MARGIN, SS_NOPREFIX, STATIC_CLASS, TEXT_WIDTH,
+    Template, get_item_secret, get_item_text, text_height,
+};
+use crate::{BlockingDialogError, BlockingLoginDialog, Credentials};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::cell::{Cell, RefCell};
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::{
+    ES_AUTOHSCROLL, ES_PASSWORD, EndDialog, GetDlgItem, IDCANCEL, IDOK, SendMessageW, WM_COMMAND,
+    WM_INITDIALOG, WM_NEXTDLGCTL, WS_BORDER, WS_TABSTOP,
+};
+
+const MESSAGE_ID: u16 = 10;
+const USERNAME_LABEL_ID: u16 = 11;
+const USERNAME_ID: u16 = 12;
+const PASSWORD_LABEL_ID: u16 = 13;
+const PASSWORD_ID: u16 = 14;
+
+const LABEL_WIDTH: i32 = 50;
+
+thread_local! {
+    /// The entry focused first, and what was typed once OK is clicked
+    static FOCUS: Cell<u16> = const { Cell::new(USERNAME_ID) };
+    static CREDENTIALS: RefCell<Option<Credentials>> = const { RefCell::new(None) };
+}
+
+unsafe extern "system" fn dialog_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    _lparam: LPARAM,
+) -> isize {
+    match msg {
+        WM_INITDIALOG => {
+            if let Ok(edit) = unsafe { GetDlgItem(Some(hwnd), FOCUS.get() as i32) } {
+                unsafe {
+                    SendMessageW(
+                        hwnd,
+                        WM_NEXTDLGCTL,
+                        Some(WPARAM(edit.0 as usize)),
+                        Some(LPARAM(1)),
+                    )
+                };
+            }
+
+            // Focus was already moved to the entry
+            0
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xffff) as i32;
+
+            if id == IDOK.0 {
+                CREDENTIALS.set(Some(Credentials {
+                    username: get_item_text(hwnd, USERNAME_ID),
+                    password: get_item_secret(hwnd, PASSWORD_ID),
+                }));
+                let _ = unsafe { EndDialog(hwnd, 1) };
+            } else if id == IDCANCEL.0 {
+                let _ = unsafe { EndDialog(hwnd, 0) };
+            }
+
+            1
+        }
+        _ => 0,
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingLoginDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Credentials>, BlockingDialogError> {
+        let hwnd = get_hwnd(&self.window)?;
+
+        let text_height = text_height(self.message, TEXT_WIDTH);
+        let username_y = MARGIN + text_height + MARGIN;
+        let password_y = username_y + CONTROL_HEIGHT + MARGIN;
+        let button_y = password_y + CONTROL_HEIGHT + 2 * MARGIN;
+
+        let width = TEXT_WIDTH + 2 * MARGIN;
+        let height = button_y + CONTROL_HEIGHT + MARGIN;
+
+        let edit_x = MARGIN + LABEL_WIDTH;
+        let edit_width = TEXT_WIDTH - LABEL_WIDTH;
+        let edit_style = WS_BORDER.0 | WS_TABSTOP.0 | ES_AUTOHSCROLL as u32;
+        // Labels are centered on their entry
+        let label_offset = (CONTROL_HEIGHT - LINE_HEIGHT) / 2;
+
+        let mut template = Template::default();
+        let text_rect = (MARGIN, MARGIN, TEXT_WIDTH, text_height);
+        template.item(
+            STATIC_CLASS,
+            MESSAGE_ID,
+            SS_NOPREFIX,
+            text_rect,
+            self.message,
+        );
+
+        let label_rect = (MARGIN, username_y + label_offset, LABEL_WIDTH, LINE_HEIGHT);
+        template.item(STATIC_CLASS, USERNAME_LABEL_ID, 0, label_rect, "&Username:");
+        let edit_rect = (edit_x, username_y, edit_width, CONTROL_HEIGHT);
+        let username = self.username.unwrap_or_default();
+        template.item(EDIT_CLASS, USERNAME_ID, edit_style, edit_rect, username);
+
+        let label_rect = (MARGIN, password_y + label_offset, LABEL_WIDTH, LINE_HEIGHT);
+        template.item(STATIC_CLASS, PASSWORD_LABEL_ID, 0, label_rect, "&Password:");
+        let edit_rect = (edit_x, password_y, edit_width, CONTROL_HEIGHT);
+        let password_style = edit_style | ES_PASSWORD as u32;
+        template.item(EDIT_CLASS, PASSWORD_ID, password_style, edit_rect, "");
+
+        let buttons = [(IDOK.0 as u16, "OK"), (IDCANCEL.0 as u16, "Cancel")];
+        template.buttons(&buttons, IDOK.0 as u16, width - MARGIN, button_y);
+
+        // Start on the password when the username is already filled in
+        FOCUS.set(match self.username {
+            Some(_) => PASSWORD_ID,
+            None => USERNAME_ID,
+        });
+        CREDENTIALS.set(None);
+
+        template.show(self.title, width, height, hwnd, Some(dialog_proc))?;
+
+        Ok(CREDENTIALS.take())
+    }
+}
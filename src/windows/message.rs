@@ -0,0 +1,137 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::get_hwnd;
+use super::template::{
+    CONTROL_HEIGHT, ICON_SIZE, MARGIN, SS_ICON, SS_NOPREFIX, STATIC_CLASS, TEXT_WIDTH, Template,
+    buttons_width, text_height,
+};
+use crate::{BlockingDialogError, BlockingDialogLevel, BlockingMessageDialog};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::cell::Cell;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::{
+    DM_SETDEFID, EndDialog, GetDlgItem, HICON, IDCANCEL, IDI_ERROR, IDI_INFORMATION, IDI_WARNING,
+    LoadIconW, STM_SETICON, SendDlgItemMessageW, SendMessageW, WM_COMMAND, WM_INITDIALOG,
+    WM_NEXTDLGCTL,
+};
+use windows::core::PCWSTR;
+
+const ICON_ID: u16 = 10;
+const MESSAGE_ID: u16 = 11;
+/// Buttons get consecutive ids from here, clear of IDOK and IDCANCEL
+const FIRST_BUTTON_ID: u16 = 100;
+
+thread_local! {
+    /// Default button, cancel button and icon of the dialog being shown
+    static STATE: Cell<(usize, usize, Option<HICON>)> = const { Cell::new((0, 0, None)) };
+}
+
+fn get_icon(level: BlockingDialogLevel) -> PCWSTR {
+    match level {
+        BlockingDialogLevel::Info => IDI_INFORMATION,
+        BlockingDialogLevel::Warning => IDI_WARNING,
+        BlockingDialogLevel::Error => IDI_ERROR,
+    }
+}
+
+unsafe extern "system" fn dialog_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    _lparam: LPARAM,
+) -> isize {
+    let (default_button, cancel_button, icon) = STATE.get();
+
+    match msg {
+        WM_INITDIALOG => {
+            let id = FIRST_BUTTON_ID as usize + default_button;
+
+            unsafe {
+                if let Some(icon) = icon {
+                    SendDlgItemMessageW(
+                        hwnd,
+                        ICON_ID as i32,
+                        STM_SETICON,
+                        WPARAM(icon.0 as usize),
+                        LPARAM(0),
+                    );
+                }
+
+                SendMessageW(hwnd, DM_SETDEFID, Some(WPARAM(id)), None);
+
+                if let Ok(button) = GetDlgItem(Some(hwnd), id as i32) {
+                    SendMessageW(
+                        hwnd,
+                        WM_NEXTDLGCTL,
+                        Some(WPARAM(button.0 as usize)),
+                        Some(LPARAM(1)),
+                    );
+                }
+            }
+
+            // Focus was already moved to the default button
+            0
+        }
+        WM_COMMAND => {
+            let id = wparam.0 & 0xffff;
+
+            // Escape and the close button send IDCANCEL
+            let index = if id == IDCANCEL.0 as usize {
+                Some(cancel_button)
+            } else {
+                id.checked_sub(FIRST_BUTTON_ID as usize)
+            };
+
+            if let Some(index) = index {
+                let _ = unsafe { EndDialog(hwnd, index as isize) };
+            }
+
+            1
+        }
+        _ => 0,
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingMessageDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<usize, BlockingDialogError> {
+        let buttons = self.get_buttons();
+        let default_button = self.get_default_button();
+        let cancel_button = self.get_cancel_button();
+
+        let hwnd = get_hwnd(&self.window)?;
+
+        let text_x = MARGIN + ICON_SIZE + MARGIN;
+        let text_height = text_height(self.message, TEXT_WIDTH).max(ICON_SIZE);
+
+        let width = (text_x + TEXT_WIDTH + MARGIN).max(buttons_width(buttons) + 2 * MARGIN);
+        let button_y = MARGIN + text_height + 2 * MARGIN;
+        let height = button_y + CONTROL_HEIGHT + MARGIN;
+
+        let mut template = Template::default();
+        let icon_rect = (MARGIN, MARGIN, ICON_SIZE, ICON_SIZE);
+        template.item(STATIC_CLASS, ICON_ID, SS_ICON, icon_rect, "");
+        let text_rect = (text_x, MARGIN, TEXT_WIDTH, text_height);
+        template.item(
+            STATIC_CLASS,
+            MESSAGE_ID,
+            SS_NOPREFIX,
+            text_rect,
+            self.message,
+        );
+
+        let buttons = buttons
+            .iter()
+            .enumerate()
+            .map(|(i, label)| (FIRST_BUTTON_ID + i as u16, *label))
+            .collect::<Vec<_>>();
+        let default_id = FIRST_BUTTON_ID + default_button as u16;
+        template.buttons(&buttons, default_id, width - MARGIN, button_y);
+
+        let icon = unsafe { LoadIconW(None, get_icon(self.level)) }.ok();
+        STATE.set((default_button, cancel_button, icon));
+
+        let res = template.show(self.title, width, height, hwnd, Some(dialog_proc))?;
+        Ok(res as usize)
+    }
+}
@@ -2,10 +2,30 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 pub mod alert;
+pub mod button_labels;
+pub mod color;
 pub mod confirm;
+pub mod date;
+pub mod form;
+pub mod input;
+pub mod list;
+pub mod login;
+pub mod message;
+pub mod password;
 pub mod pick_directory;
 pub mod pick_files;
+pub mod progress;
+pub mod question;
 pub mod save_file;
+pub mod template;
+
+use crate::{BlockingDialogError, BlockingDialogLevel};
+use raw_window_handle::{HandleError, HasWindowHandle, RawWindowHandle};
+use std::ffi::c_void;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::{
+    MB_ICONERROR, MB_ICONINFORMATION, MB_ICONWARNING, MESSAGEBOX_STYLE,
+};
 
 pub fn widen<S: AsRef<str>>(s: S) -> Vec<u16> {
     s.as_ref()
@@ -19,3 +39,26 @@ pub fn unwiden<I: IntoIterator<Item = u16>>(s: I) -> String {
     let s = String::from_utf16_lossy(&vec);
     s.trim_matches(char::from(0)).to_string()
 }
+
+pub fn get_icon_style(level: BlockingDialogLevel) -> MESSAGEBOX_STYLE {
+    match level {
+        BlockingDialogLevel::Info => MB_ICONINFORMATION,
+        BlockingDialogLevel::Warning => MB_ICONWARNING,
+        BlockingDialogLevel::Error => MB_ICONERROR,
+    }
+}
+
+/// The owner window, `None` for `NoParent`
+pub fn get_hwnd<W: HasWindowHandle>(window: &W) -> Result<Option<HWND>, BlockingDialogError> {
+    let w = match window.window_handle() {
+        Ok(w) => w,
+        Err(HandleError::Unavailable) => return Ok(None),
+        Err(err) => return Err(BlockingDialogError::Handle(err)),
+    };
+
+    let RawWindowHandle::Win32(handle) = w.as_raw() else {
+        return Err(BlockingDialogError::Handle(HandleError::NotSupported));
+    };
+
+    Ok(Some(HWND(handle.hwnd.get() as *mut c_void)))
+}
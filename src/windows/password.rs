@@ -0,0 +1,16 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::get_hwnd;
+use super::input::show_entry;
+use crate::{BlockingDialogError, BlockingPasswordDialog, Secret};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use windows::Win32::UI::WindowsAndMessaging::ES_PASSWORD;
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPasswordDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<Secret>, BlockingDialogError> {
+        let hwnd = get_hwnd(&self.window)?;
+
+        show_entry(hwnd, self.title, self.message, "", "", ES_PASSWORD as u32)
+    }
+}
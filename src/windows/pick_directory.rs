@@ -1,38 +1,53 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use super::{unwiden, widen};
+use super::{get_hwnd, unwiden, widen};
 use crate::{BlockingDialogError, BlockingPickDirectoryDialog};
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
-use std::ffi::c_void;
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::path::PathBuf;
-use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
 use windows::Win32::System::Com::CoTaskMemFree;
 use windows::Win32::UI::Shell::SHGetPathFromIDListW;
 use windows::Win32::UI::Shell::{
-    BIF_NEWDIALOGSTYLE, BIF_RETURNONLYFSDIRS, BROWSEINFOW, SHBrowseForFolderW,
+    BFFM_INITIALIZED, BFFM_SETSELECTIONW, BIF_NEWDIALOGSTYLE, BIF_RETURNONLYFSDIRS, BROWSEINFOW,
+    SHBrowseForFolderW,
 };
+use windows::Win32::UI::WindowsAndMessaging::SendMessageW;
 use windows::core::PCWSTR;
 
-impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickDirectoryDialog<'a, W> {
-    pub fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
-        let w = self
-            .window
-            .window_handle()
-            .map_err(BlockingDialogError::Handle)?;
+/// Selects the starting directory, passed as `lpdata`, once the dialog is ready
+unsafe extern "system" fn browse_callback(
+    hwnd: HWND,
+    msg: u32,
+    _lparam: LPARAM,
+    lpdata: LPARAM,
+) -> i32 {
+    if msg == BFFM_INITIALIZED && lpdata.0 != 0 {
+        unsafe { SendMessageW(hwnd, BFFM_SETSELECTIONW, Some(WPARAM(1)), Some(lpdata)) };
+    }
 
-        let RawWindowHandle::Win32(handle) = w.as_raw() else {
-            return Err(BlockingDialogError::Handle(HandleError::NotSupported));
-        };
+    0
+}
 
-        let hwnd = HWND(handle.hwnd.get() as *mut c_void);
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickDirectoryDialog<'a, W> {
+    pub(crate) fn show_native(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+        let hwnd = get_hwnd(&self.window)?;
 
         let title_wide = widen(self.title);
+        let initial_dir_wide = self
+            .starting_directory
+            .map(|dir| widen(dir.to_string_lossy()));
 
         let mut browse_info = BROWSEINFOW {
-            hwndOwner: hwnd,
+            hwndOwner: hwnd.unwrap_or_default(),
             lpszTitle: PCWSTR(title_wide.as_ptr()),
             ulFlags: BIF_RETURNONLYFSDIRS | BIF_NEWDIALOGSTYLE,
+            lpfn: Some(browse_callback),
+            lParam: LPARAM(
+                initial_dir_wide
+                    .as_ref()
+                    .map_or(0, |dir| dir.as_ptr() as isize),
+            ),
             ..Default::default()
         };
 
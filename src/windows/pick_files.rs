@@ -1,12 +1,10 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use super::widen;
+use super::{get_hwnd, widen};
 use crate::{BlockingDialogError, BlockingPickFilesDialog, BlockingPickFilesDialogFilter};
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
-use std::ffi::c_void;
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::path::PathBuf;
-use windows::Win32::Foundation::HWND;
 use windows::{
     Win32::UI::Controls::Dialogs::{
         GetOpenFileNameW, OFN_ALLOWMULTISELECT, OFN_EXPLORER, OFN_FILEMUSTEXIST, OFN_PATHMUSTEXIST,
@@ -58,20 +56,14 @@ fn parse_multi_select(buffer: &[u16]) -> Vec<PathBuf> {
 }
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickFilesDialog<'a, W> {
-    pub fn show(&self) -> Result<Vec<PathBuf>, BlockingDialogError> {
+    pub(crate) fn show_native(&self) -> Result<Vec<PathBuf>, BlockingDialogError> {
         let title_wide = widen(self.title);
         let filter_wide = get_filter_utf16(&self.filter);
+        let initial_dir_wide = self
+            .starting_directory
+            .map(|dir| widen(dir.to_string_lossy()));
 
-        let w = self
-            .window
-            .window_handle()
-            .map_err(BlockingDialogError::Handle)?;
-
-        let RawWindowHandle::Win32(handle) = w.as_raw() else {
-            return Err(BlockingDialogError::Handle(HandleError::NotSupported));
-        };
-
-        let hwnd = HWND(handle.hwnd.get() as *mut c_void);
+        let hwnd = get_hwnd(&self.window)?;
 
         let mut file_buffer = vec![0u16; 32_768];
 
@@ -83,11 +75,14 @@ impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingPickFilesDialog<'a, W> {
         let result = unsafe {
             let mut ofn = OPENFILENAMEW {
                 lStructSize: std::mem::size_of::<OPENFILENAMEW>() as u32,
-                hwndOwner: hwnd,
+                hwndOwner: hwnd.unwrap_or_default(),
                 lpstrFilter: PCWSTR(filter_wide.as_ptr()),
                 lpstrFile: PWSTR(file_buffer.as_mut_ptr()),
                 nMaxFile: file_buffer.len() as u32,
                 lpstrTitle: PCWSTR(title_wide.as_ptr()),
+                lpstrInitialDir: initial_dir_wide
+                    .as_ref()
+                    .map_or(PCWSTR::null(), |dir| PCWSTR(dir.as_ptr())),
                 Flags: flags,
                 ..Default::default()
             };
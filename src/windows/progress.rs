@@ -0,0 +1,165 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::get_hwnd;
+use super::template::{
+    CONTROL_HEIGHT, LINE_HEIGHT, MARGIN, PROGRESS_CLASS, SS_NOPREFIX, STATIC_CLASS, TEXT_WIDTH,
+    Template, init_common_controls, set_item_text, text_height,
+};
+use crate::progress::{POLL_INTERVAL, ProgressState};
+use crate::{BlockingDialogError, BlockingProgressDialog, Progress};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::cell::RefCell;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::Controls::{
+    ICC_PROGRESS_CLASS, PBM_SETMARQUEE, PBM_SETPOS, PBM_SETRANGE32, PBS_MARQUEE,
+};
+use windows::Win32::UI::Input::KeyboardAndMouse::EnableWindow;
+use windows::Win32::UI::WindowsAndMessaging::{
+    EndDialog, GetDlgItem, IDCANCEL, KillTimer, SW_HIDE, SW_SHOW, SendDlgItemMessageW, SetTimer,
+    ShowWindow, WM_COMMAND, WM_INITDIALOG, WM_TIMER,
+};
+
+const MESSAGE_ID: u16 = 10;
+/// The bar showing the fraction, swapped with the marquee one while pulsing
+const BAR_ID: u16 = 11;
+const PULSE_ID: u16 = 12;
+
+const TIMER_ID: usize = 1;
+const BAR_HEIGHT: i32 = 10;
+/// Steps of the bar, finer than percents
+const BAR_RANGE: f64 = 1000.0;
+
+thread_local! {
+    /// The worker's progress, and the state the dialog last showed
+    static PROGRESS: RefCell<Option<Progress>> = const { RefCell::new(None) };
+    static SHOWN: RefCell<Option<ProgressState>> = const { RefCell::new(None) };
+}
+
+fn show_item(hwnd: HWND, id: u16, show: bool) {
+    if let Ok(item) = unsafe { GetDlgItem(Some(hwnd), id as i32) } {
+        let _ = unsafe { ShowWindow(item, if show { SW_SHOW } else { SW_HIDE }) };
+    }
+}
+
+fn update(hwnd: HWND, state: &ProgressState) {
+    if let Some(text) = &state.text {
+        set_item_text(hwnd, MESSAGE_ID, text);
+    }
+
+    show_item(hwnd, BAR_ID, state.fraction.is_some());
+    show_item(hwnd, PULSE_ID, state.fraction.is_none());
+
+    if let Some(fraction) = state.fraction {
+        let pos = WPARAM((fraction * BAR_RANGE).round() as usize);
+        unsafe { SendDlgItemMessageW(hwnd, BAR_ID as i32, PBM_SETPOS, pos, LPARAM(0)) };
+    }
+}
+
+unsafe extern "system" fn dialog_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    _lparam: LPARAM,
+) -> isize {
+    match msg {
+        WM_INITDIALOG => {
+            unsafe {
+                let range = LPARAM(BAR_RANGE as isize);
+                SendDlgItemMessageW(hwnd, BAR_ID as i32, PBM_SETRANGE32, WPARAM(0), range);
+                SendDlgItemMessageW(hwnd, PULSE_ID as i32, PBM_SETMARQUEE, WPARAM(1), LPARAM(0));
+                SetTimer(Some(hwnd), TIMER_ID, POLL_INTERVAL.as_millis() as u32, None);
+            }
+
+            show_item(hwnd, PULSE_ID, false);
+
+            // Let the dialog focus the cancel button
+            1
+        }
+        WM_TIMER => {
+            let Some(progress) = PROGRESS.with_borrow(Clone::clone) else {
+                return 0;
+            };
+
+            if progress.is_finished() {
+                let _ = unsafe { KillTimer(Some(hwnd), TIMER_ID) };
+                let _ = unsafe { EndDialog(hwnd, 1) };
+                return 0;
+            }
+
+            let state = progress.get_state();
+
+            if SHOWN.with_borrow(|shown| shown.as_ref() != Some(&state)) {
+                update(hwnd, &state);
+                SHOWN.set(Some(state));
+            }
+
+            0
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xffff) as i32;
+
+            // The dialog stays until the worker returns, the timer closes it
+            if id == IDCANCEL.0 {
+                if let Some(progress) = PROGRESS.with_borrow(Clone::clone) {
+                    progress.cancel();
+                }
+
+                if let Ok(button) = unsafe { GetDlgItem(Some(hwnd), IDCANCEL.0) } {
+                    let _ = unsafe { EnableWindow(button, false) };
+                }
+            }
+
+            1
+        }
+        _ => 0,
+    }
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingProgressDialog<'a, W> {
+    pub(crate) fn prepare_native(&self) -> Result<Option<HWND>, BlockingDialogError> {
+        get_hwnd(&self.window)
+    }
+
+    pub(crate) fn show_native(
+        &self,
+        hwnd: Option<HWND>,
+        progress: &Progress,
+    ) -> Result<(), BlockingDialogError> {
+        init_common_controls(ICC_PROGRESS_CLASS);
+
+        // Room for a second line, as the worker's text may be longer
+        let text_height = text_height(self.message, TEXT_WIDTH).max(2 * LINE_HEIGHT);
+
+        let mut template = Template::default();
+        let text_rect = (MARGIN, MARGIN, TEXT_WIDTH, text_height);
+        template.item(
+            STATIC_CLASS,
+            MESSAGE_ID,
+            SS_NOPREFIX,
+            text_rect,
+            self.message,
+        );
+
+        let bar_y = MARGIN + text_height + MARGIN;
+        let bar_rect = (MARGIN, bar_y, TEXT_WIDTH, BAR_HEIGHT);
+        template.named_item(PROGRESS_CLASS, BAR_ID, 0, bar_rect, "");
+        template.named_item(PROGRESS_CLASS, PULSE_ID, PBS_MARQUEE, bar_rect, "");
+
+        let width = TEXT_WIDTH + 2 * MARGIN;
+        let button_y = bar_y + BAR_HEIGHT + 2 * MARGIN;
+        let height = button_y + CONTROL_HEIGHT + MARGIN;
+
+        let buttons = [(IDCANCEL.0 as u16, "Cancel")];
+        template.buttons(&buttons, IDCANCEL.0 as u16, width - MARGIN, button_y);
+
+        PROGRESS.set(Some(progress.clone()));
+        SHOWN.set(None);
+
+        let res = template.show(self.title, width, height, hwnd, Some(dialog_proc));
+        PROGRESS.set(None);
+        res?;
+
+        Ok(())
+    }
+}
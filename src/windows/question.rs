@@ -0,0 +1,46 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::{get_hwnd, get_icon_style, widen};
+use crate::{
+    BlockingDialogError, BlockingDialogLevel, BlockingQuestionDialog,
+    BlockingQuestionDialogResponse,
+};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use windows::Win32::UI::WindowsAndMessaging::{
+    IDNO, IDYES, MB_YESNOCANCEL, MESSAGEBOX_STYLE, MessageBoxW,
+};
+use windows::core::PCWSTR;
+
+fn get_utype(level: BlockingDialogLevel) -> MESSAGEBOX_STYLE {
+    get_icon_style(level) | MB_YESNOCANCEL
+}
+
+impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingQuestionDialog<'a, W> {
+    pub(crate) fn show_native(
+        &self,
+    ) -> Result<BlockingQuestionDialogResponse, BlockingDialogError> {
+        let title_wide = widen(self.title);
+        let message_wide = widen(self.message);
+
+        let hwnd = get_hwnd(&self.window)?;
+
+        let res = unsafe {
+            MessageBoxW(
+                hwnd,
+                PCWSTR(message_wide.as_ptr()),
+                PCWSTR(title_wide.as_ptr()),
+                get_utype(self.level),
+            )
+        };
+
+        // Closing the window returns IDCANCEL
+        let response = match res {
+            IDYES => BlockingQuestionDialogResponse::Yes,
+            IDNO => BlockingQuestionDialogResponse::No,
+            _ => BlockingQuestionDialogResponse::Cancel,
+        };
+
+        Ok(response)
+    }
+}
@@ -1,12 +1,10 @@
 // SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use super::{unwiden, widen};
+use super::{get_hwnd, unwiden, widen};
 use crate::{BlockingDialogError, BlockingPickFilesDialogFilter, BlockingSaveFileDialog};
-use raw_window_handle::{HandleError, HasDisplayHandle, HasWindowHandle, RawWindowHandle};
-use std::ffi::c_void;
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::path::PathBuf;
-use windows::Win32::Foundation::HWND;
 use windows::{
     Win32::UI::Controls::Dialogs::{
         GetSaveFileNameW, OFN_EXPLORER, OFN_OVERWRITEPROMPT, OPENFILENAMEW,
@@ -36,20 +34,14 @@ fn get_filter_utf16(filter: &[BlockingPickFilesDialogFilter]) -> Vec<u16> {
 }
 
 impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingSaveFileDialog<'a, W> {
-    pub fn show(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
+    pub(crate) fn show_native(&self) -> Result<Option<PathBuf>, BlockingDialogError> {
         let title_wide = widen(self.title);
         let filter_wide = get_filter_utf16(&self.filter);
+        let initial_dir_wide = self
+            .starting_directory
+            .map(|dir| widen(dir.to_string_lossy()));
 
-        let w = self
-            .window
-            .window_handle()
-            .map_err(BlockingDialogError::Handle)?;
-
-        let RawWindowHandle::Win32(handle) = w.as_raw() else {
-            return Err(BlockingDialogError::Handle(HandleError::NotSupported));
-        };
-
-        let hwnd = HWND(handle.hwnd.get() as *mut c_void);
+        let hwnd = get_hwnd(&self.window)?;
 
         let mut file_buffer = [0u16; 260];
 
@@ -62,11 +54,14 @@ impl<'a, W: HasWindowHandle + HasDisplayHandle> BlockingSaveFileDialog<'a, W> {
         let result = unsafe {
             let mut ofn = OPENFILENAMEW {
                 lStructSize: std::mem::size_of::<OPENFILENAMEW>() as u32,
-                hwndOwner: hwnd,
+                hwndOwner: hwnd.unwrap_or_default(),
                 lpstrFilter: PCWSTR(filter_wide.as_ptr()),
                 lpstrFile: PWSTR(file_buffer.as_mut_ptr()),
                 nMaxFile: file_buffer.len() as u32,
                 lpstrTitle: PCWSTR(title_wide.as_ptr()),
+                lpstrInitialDir: initial_dir_wide
+                    .as_ref()
+                    .map_or(PCWSTR::null(), |dir| PCWSTR(dir.as_ptr())),
                 Flags: OFN_EXPLORER | OFN_OVERWRITEPROMPT,
                 ..Default::default()
             };
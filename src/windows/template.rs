@@ -0,0 +1,270 @@
+// SPDX-FileCopyrightText: 2026 Manuel Quarneti <mq1@ik.me>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use super::widen;
+use crate::secret::zeroize;
+use crate::{BlockingDialogError, Secret};
+use std::char::REPLACEMENT_CHARACTER;
+use std::io;
+use windows::Win32::Foundation::{HWND, LPARAM};
+use windows::Win32::UI::Controls::{
+    INITCOMMONCONTROLSEX, INITCOMMONCONTROLSEX_ICC, InitCommonControlsEx,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    BS_DEFPUSHBUTTON, BS_PUSHBUTTON, DLGPROC, DLGTEMPLATE, DS_CENTER, DS_MODALFRAME, DS_SETFONT,
+    DialogBoxIndirectParamW, GetDlgItem, GetDlgItemTextW, GetWindowTextLengthW, GetWindowTextW,
+    SetDlgItemTextW, SetWindowTextW, WS_CAPTION, WS_CHILD, WS_POPUP, WS_SYSMENU, WS_TABSTOP,
+    WS_VISIBLE,
+};
+use windows::core::{PCWSTR, w};
+
+// Predefined window class atoms for dialog template items
+pub const BUTTON_CLASS: u16 = 0x0080;
+pub const EDIT_CLASS: u16 = 0x0081;
+pub const STATIC_CLASS: u16 = 0x0082;
+pub const COMBOBOX_CLASS: u16 = 0x0085;
+// Registered by `init_common_controls` rather than predefined
+pub const DATE_PICKER_CLASS: &str = "SysDateTimePick32";
+pub const LIST_VIEW_CLASS: &str = "SysListView32";
+pub const PROGRESS_CLASS: &str = "msctls_progress32";
+
+// The windows crate only exposes these under Win32_System_SystemServices
+pub const SS_ICON: u32 = 0x3;
+pub const SS_NOPREFIX: u32 = 0x80;
+
+// Layout in dialog units, where the average character is 4 units wide
+pub const MARGIN: i32 = 7;
+pub const ICON_SIZE: i32 = 21;
+pub const TEXT_WIDTH: i32 = 220;
+pub const LINE_HEIGHT: i32 = 8;
+pub const CONTROL_HEIGHT: i32 = 14;
+pub const BUTTON_MIN_WIDTH: i32 = 50;
+pub const BUTTON_GAP: i32 = 4;
+
+/// Registers the classes of common controls, like `DATE_PICKER_CLASS`
+pub fn init_common_controls(classes: INITCOMMONCONTROLSEX_ICC) {
+    // Unlike the predefined classes, these aren't registered until common
+    // controls are initialized
+    let icc = INITCOMMONCONTROLSEX {
+        dwSize: std::mem::size_of::<INITCOMMONCONTROLSEX>() as u32,
+        dwICC: classes,
+    };
+    let _ = unsafe { InitCommonControlsEx(&icc) };
+}
+
+pub fn text_width(s: &str) -> i32 {
+    (s.chars().count() as i32).saturating_mul(4)
+}
+
+/// Height of a static control showing `text` wrapped to `width`
+pub fn text_height(text: &str, width: i32) -> i32 {
+    text.lines()
+        .map(|line| text_width(line) / width + 1)
+        .sum::<i32>()
+        .max(1)
+        .saturating_mul(LINE_HEIGHT)
+}
+
+pub fn button_width(label: &str) -> i32 {
+    (text_width(label) + 12).max(BUTTON_MIN_WIDTH)
+}
+
+pub fn buttons_width(labels: &[&str]) -> i32 {
+    labels.iter().map(|label| button_width(label)).sum::<i32>()
+        + BUTTON_GAP * (labels.len() as i32 - 1).max(0)
+}
+
+struct Item {
+    /// Either 0xffff and a class atom, or a NUL-terminated class name
+    class: Vec<u16>,
+    id: u16,
+    style: u32,
+    rect: (i32, i32, i32, i32),
+    text: String,
+}
+
+/// An in-memory `DLGTEMPLATE`, for dialogs that `MessageBoxW` can't show
+#[derive(Default)]
+pub struct Template {
+    items: Vec<Item>,
+}
+
+impl Template {
+    /// Adds a visible child control, `rect` is `(x, y, width, height)`
+    pub fn item(
+        &mut self,
+        class: u16,
+        id: u16,
+        style: u32,
+        rect: (i32, i32, i32, i32),
+        text: &str,
+    ) {
+        self.items.push(Item {
+            class: vec![0xffff, class],
+            id,
+            style: style | WS_CHILD.0 | WS_VISIBLE.0,
+            rect,
+            text: text.to_string(),
+        });
+    }
+
+    /// Like `item`, for controls without a predefined class atom
+    pub fn named_item(
+        &mut self,
+        class: &str,
+        id: u16,
+        style: u32,
+        rect: (i32, i32, i32, i32),
+        text: &str,
+    ) {
+        self.item(0, id, style, rect, text);
+
+        if let Some(item) = self.items.last_mut() {
+            item.class = widen(class);
+        }
+    }
+
+    /// Adds a row of push buttons ending at `right`
+    pub fn buttons(&mut self, buttons: &[(u16, &str)], default_id: u16, right: i32, y: i32) {
+        let labels = buttons.iter().map(|(_, label)| *label).collect::<Vec<_>>();
+        let mut x = right - buttons_width(&labels);
+
+        for (id, label) in buttons {
+            let style = if *id == default_id {
+                BS_DEFPUSHBUTTON as u32
+            } else {
+                BS_PUSHBUTTON as u32
+            };
+
+            let width = button_width(label);
+            self.item(
+                BUTTON_CLASS,
+                *id,
+                style | WS_TABSTOP.0,
+                (x, y, width, CONTROL_HEIGHT),
+                label,
+            );
+            x += width + BUTTON_GAP;
+        }
+    }
+
+    fn build(&self, title: &str, width: i32, height: i32) -> Vec<u32> {
+        let mut words = Vec::<u16>::new();
+
+        let push_u32 = |words: &mut Vec<u16>, v: u32| {
+            words.push(v as u16);
+            words.push((v >> 16) as u16);
+        };
+        let push_rect = |words: &mut Vec<u16>, (x, y, cx, cy): (i32, i32, i32, i32)| {
+            for v in [x, y, cx, cy] {
+                words.push(v.clamp(0, i16::MAX as i32) as u16);
+            }
+        };
+
+        let style = DS_SETFONT as u32
+            | DS_MODALFRAME as u32
+            | DS_CENTER as u32
+            | WS_POPUP.0
+            | WS_CAPTION.0
+            | WS_SYSMENU.0;
+
+        push_u32(&mut words, style);
+        push_u32(&mut words, 0);
+        words.push(self.items.len() as u16);
+        push_rect(&mut words, (0, 0, width, height));
+        // No menu and the default dialog class
+        words.extend([0, 0]);
+        words.extend(widen(title));
+        words.push(8);
+        words.extend(widen("MS Shell Dlg"));
+
+        for item in &self.items {
+            if !words.len().is_multiple_of(2) {
+                words.push(0);
+            }
+
+            push_u32(&mut words, item.style);
+            push_u32(&mut words, 0);
+            push_rect(&mut words, item.rect);
+            words.push(item.id);
+            words.extend(&item.class);
+            words.extend(widen(&item.text));
+            words.push(0);
+        }
+
+        words
+            .chunks(2)
+            .map(|pair| pair[0] as u32 | (pair.get(1).copied().unwrap_or(0) as u32) << 16)
+            .collect()
+    }
+
+    /// Runs the dialog modally and returns what the dialog procedure passed to `EndDialog`
+    pub fn show(
+        &self,
+        title: &str,
+        width: i32,
+        height: i32,
+        parent: Option<HWND>,
+        dialog_proc: DLGPROC,
+    ) -> Result<isize, BlockingDialogError> {
+        let template = self.build(title, width, height);
+
+        let res = unsafe {
+            DialogBoxIndirectParamW(
+                None,
+                template.as_ptr() as *const DLGTEMPLATE,
+                parent,
+                dialog_proc,
+                LPARAM(0),
+            )
+        };
+
+        if res == -1 {
+            return Err(BlockingDialogError::Io(io::Error::last_os_error()));
+        }
+
+        Ok(res)
+    }
+}
+
+pub fn get_item_text(hwnd: HWND, id: u16) -> String {
+    let len = match unsafe { GetDlgItem(Some(hwnd), id as i32) } {
+        Ok(item) => unsafe { GetWindowTextLengthW(item) },
+        Err(_) => 0,
+    };
+
+    let mut buffer = vec![0u16; len.max(0) as usize + 1];
+    let copied = unsafe { GetDlgItemTextW(hwnd, id as i32, &mut buffer) };
+
+    String::from_utf16_lossy(&buffer[..copied as usize])
+}
+
+/// Reads a password entry straight into a `Secret`, then wipes both the
+/// copied text and the entry itself
+pub fn get_item_secret(hwnd: HWND, id: u16) -> Secret {
+    let Ok(item) = (unsafe { GetDlgItem(Some(hwnd), id as i32) }) else {
+        return Secret::new(String::new());
+    };
+
+    let len = unsafe { GetWindowTextLengthW(item) };
+    let mut buffer = vec![0u16; len.max(0) as usize + 1];
+    let copied = unsafe { GetWindowTextW(item, &mut buffer) };
+    let units = &buffer[..copied.max(0) as usize];
+
+    // Sized for the longest encoding so the string is never reallocated,
+    // which would leave a copy behind
+    let mut secret = String::with_capacity(units.len() * 3);
+    let chars = char::decode_utf16(units.iter().copied());
+    secret.extend(chars.map(|c| c.unwrap_or(REPLACEMENT_CHARACTER)));
+    let secret = Secret::new(secret);
+
+    zeroize(&mut buffer);
+    let _ = unsafe { SetWindowTextW(item, w!("")) };
+
+    secret
+}
+
+pub fn set_item_text(hwnd: HWND, id: u16, text: &str) {
+    let text = widen(text);
+    let _ = unsafe { SetDlgItemTextW(hwnd, id as i32, PCWSTR(text.as_ptr())) };
+}